@@ -0,0 +1,139 @@
+//! Arpeggios of the diatonic chords of a scale, shown inside the same window of frets.
+//!
+//! The chord on a degree stacks thirds within the scale: the root, third, fifth and seventh are
+//! the degree itself and every other scale note above it. The other scale notes stay on the
+//! fret board but are dimmed, so switching between the scale and its arpeggios keeps the
+//! fingering in place.
+
+use crate::{
+    fret_board::{FretBoard, Highlight},
+    notes::Note,
+};
+
+/// Number of chord tones, from the root to the seventh.
+pub const CHORD_SIZE: usize = 4;
+
+/// Fewest notes a scale needs for its chords to be stacked in thirds.
+const MIN_DIATONIC_NOTES: usize = 7;
+
+/// Whether the scale, given by its semitone steps, has enough notes for its chords to be
+/// stacked in thirds.
+pub fn fits_arpeggio(steps: &[usize]) -> bool {
+    steps.len() >= MIN_DIATONIC_NOTES
+}
+
+/// The root, third, fifth and seventh of the chord on `degree` of the scale, 1 being the root
+/// of the scale, each with its semitone step from the root of the scale.
+pub fn chord_tones(
+    notes_in_scale: &[(Note, usize)],
+    degree: usize,
+) -> Result<Vec<(Note, usize)>, String> {
+    let num_notes = notes_in_scale.len();
+    if num_notes < MIN_DIATONIC_NOTES {
+        return Err(format!(
+            "Arpeggios need a scale with at least {} notes, this one has {}",
+            MIN_DIATONIC_NOTES, num_notes
+        ));
+    }
+    if !(1..=num_notes).contains(&degree) {
+        return Err(format!(
+            "The scale has no degree {}, it has {} notes",
+            degree, num_notes
+        ));
+    }
+    Ok((0..CHORD_SIZE)
+        .map(|tone| notes_in_scale[(degree - 1 + 2 * tone) % num_notes])
+        .collect())
+}
+
+/// Dims every scale note of the fret board that is not one of the `chord_tones`.
+pub fn mark_arpeggio(fret_board: &mut FretBoard, chord_tones: &[(Note, usize)]) {
+    for string in &mut fret_board.strings {
+        for position in &mut string.positions {
+            if position.step.is_some()
+                && !chord_tones.iter().any(|(note, _)| *note == position.note)
+            {
+                position.highlight = Highlight::Dimmed;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        format::Format,
+        fret_board::{build_fret_board, render_fret_board, FRET_SPAN},
+        scales::Scale,
+        spelling::Spelling,
+        tunings::Tuning,
+    };
+
+    #[test]
+    fn test_chord_tones() {
+        let c_major = Scale::Major.get_notes(Note::C);
+        let notes = |degree| -> Vec<Note> {
+            chord_tones(&c_major, degree)
+                .unwrap()
+                .into_iter()
+                .map(|(note, _)| note)
+                .collect()
+        };
+        assert_eq!(notes(1), vec![Note::C, Note::E, Note::G, Note::B]);
+        assert_eq!(notes(5), vec![Note::G, Note::B, Note::D, Note::F]);
+        assert_eq!(notes(7), vec![Note::B, Note::D, Note::F, Note::A]);
+        assert_eq!(
+            chord_tones(&c_major, 2).unwrap()[1],
+            (Note::F, 5),
+            "steps are counted from the root of the scale"
+        );
+        assert!(chord_tones(&c_major, 0).is_err());
+        assert!(chord_tones(&c_major, 8).is_err());
+        assert!(chord_tones(&Scale::PentatonicMinor.get_notes(Note::A), 1).is_err());
+        assert!(fits_arpeggio(Scale::WholeDiminished.get_steps()));
+        assert!(!fits_arpeggio(Scale::PentatonicBlues.get_steps()));
+    }
+
+    #[test]
+    fn test_mark_arpeggio() {
+        let notes = Scale::NaturalMinor.get_notes(Note::A);
+        let mut fret_board =
+            build_fret_board(&Tuning::StandardE6.get_pitches(), 5, FRET_SPAN, &notes);
+        let num_scale_positions = fret_board.scale_positions().count();
+        mark_arpeggio(&mut fret_board, &chord_tones(&notes, 1).unwrap());
+        assert_eq!(fret_board.scale_positions().count(), num_scale_positions);
+        let lit: Vec<Note> = fret_board.strings[0]
+            .positions
+            .iter()
+            .filter(|position| position.step.is_some() && position.highlight == Highlight::Normal)
+            .map(|position| position.note)
+            .collect();
+        assert_eq!(lit, vec![Note::A, Note::C]);
+        assert!(fret_board
+            .strings
+            .iter()
+            .flat_map(|string| &string.positions)
+            .all(|position| position.step.is_some() || position.highlight == Highlight::Normal));
+    }
+
+    #[test]
+    fn test_render_arpeggio_uncolored() {
+        let notes = Scale::Major.get_notes(Note::C);
+        let mut fret_board =
+            build_fret_board(&Tuning::StandardE6.get_pitches(), 7, FRET_SPAN, &notes);
+        mark_arpeggio(&mut fret_board, &chord_tones(&notes, 5).unwrap());
+        let lines = render_fret_board(
+            &fret_board,
+            &Format {
+                spelling: Spelling::Sharps,
+                colored: false,
+                ..Default::default()
+            },
+        );
+        // G, B, D and F as usual, the other notes of C major between dots.
+        assert_eq!(lines[0], "|---B----|---.C.--|--------|---D---|-------|");
+        assert_eq!(lines[5], "|===B====|===.C.==|========|===D===|=======|");
+        assert_eq!(lines[3], "|===.A.==|========|===B====|===.C.=|=======|");
+    }
+}
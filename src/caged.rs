@@ -0,0 +1,193 @@
+//! The CAGED system, which splits the neck into five overlapping shapes named after the open
+//! chords C, A, G, E and D.
+//!
+//! Every shape is the scale played around its chord shape moved up the neck until the chord
+//! has the right root. Where the chord shape sits follows from the tuning: the root of the E
+//! shape lies on the sixth string counted from the highest, so in drop D it moves up two frets
+//! compared to standard tuning.
+
+use clap::ValueEnum;
+use std::collections::HashSet;
+use strum::{Display, EnumIter};
+
+use crate::{
+    fret_board::{build_fret_board, FretBoard, NUM_FRETS},
+    notes::{Note, NUM_NOTES},
+    pitch::Pitch,
+};
+
+/// Number of strings the shapes are laid out over, counted from the highest.
+const NUM_CAGED_STRINGS: usize = 6;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, ValueEnum, EnumIter, Display)]
+pub enum CagedShape {
+    C,
+    A,
+    G,
+    E,
+    D,
+}
+
+impl CagedShape {
+    /// The string holding the lowest root of the open chord, counted from the highest string
+    /// starting at 1, and the fret of that root in the open chord.
+    fn root(&self) -> (usize, usize) {
+        match self {
+            CagedShape::C => (5, 3),
+            CagedShape::A => (5, 0),
+            CagedShape::G => (6, 3),
+            CagedShape::E => (6, 0),
+            CagedShape::D => (4, 0),
+        }
+    }
+
+    /// The frets covered by the scale around the open chord, where -1 is one fret below the nut.
+    fn frets(&self) -> (isize, isize) {
+        match self {
+            CagedShape::C => (0, 3),
+            CagedShape::A => (-1, 2),
+            CagedShape::G => (-1, 3),
+            CagedShape::E => (-1, 2),
+            CagedShape::D => (-1, 3),
+        }
+    }
+
+    /// The starting fret and the number of frets of the shape for `root`, in a tuning given by
+    /// its open string pitches from the lowest string to the highest.
+    ///
+    /// Shapes that would start below the nut are played in open position, from the nut with
+    /// the open strings.
+    pub fn window(&self, tuning: &[Pitch], root: Note) -> Result<(usize, usize), String> {
+        if tuning.len() < NUM_CAGED_STRINGS {
+            return Err(format!(
+                "CAGED shapes need at least {} strings",
+                NUM_CAGED_STRINGS
+            ));
+        }
+        let (root_string, root_fret) = self.root();
+        let open_note = tuning[tuning.len() - root_string].note;
+        let root_on_string = open_note.interval_to(root);
+        let nut = (root_on_string + NUM_NOTES - root_fret) % NUM_NOTES;
+        let (low, high) = self.frets();
+        let starting_fret = (nut as isize + low).max(0) as usize;
+        let fret_span = (nut as isize + high + 1) as usize - starting_fret;
+        debug_assert!(starting_fret + fret_span <= NUM_FRETS + 1);
+        Ok((starting_fret, fret_span))
+    }
+}
+
+/// Whether the scale, given by its semitone steps, contains the major triad the CAGED shapes
+/// are built around.
+pub fn fits_caged(steps: &[usize]) -> bool {
+    steps.contains(&4) && steps.contains(&7)
+}
+
+/// Lays out the scale in one CAGED shape. Where a pitch falls inside the shape on two strings,
+/// only the lower string keeps it, so every note of the shape is played once.
+pub fn build_caged_fret_board(
+    tuning: &[Pitch],
+    root: Note,
+    shape: CagedShape,
+    notes_in_scale: &[(Note, usize)],
+) -> Result<FretBoard, String> {
+    let (starting_fret, fret_span) = shape.window(tuning, root)?;
+    let mut fret_board = build_fret_board(tuning, starting_fret, fret_span, notes_in_scale);
+    let mut played = HashSet::new();
+    for string in &mut fret_board.strings {
+        for position in &mut string.positions {
+            if position.step.is_some() && !played.insert(position.pitch) {
+                position.step = None;
+            }
+        }
+    }
+    Ok(fret_board)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{scales::Scale, tunings::Tuning};
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_window() {
+        let standard = Tuning::StandardE6.get_pitches();
+        let windows: Vec<(usize, usize)> = CagedShape::iter()
+            .map(|shape| shape.window(&standard, Note::C).unwrap())
+            .collect();
+        assert_eq!(windows, vec![(0, 4), (2, 4), (4, 5), (7, 4), (9, 5)]);
+        // At the nut, the A shape of A is played in open position.
+        assert_eq!(CagedShape::A.window(&standard, Note::A), Ok((0, 3)));
+        // The E shape follows the lowest string.
+        let drop_d = Tuning::DropD6.get_pitches();
+        assert_eq!(CagedShape::E.window(&drop_d, Note::C), Ok((9, 4)));
+        assert_eq!(CagedShape::A.window(&drop_d, Note::C), Ok((2, 4)));
+        let seven_strings = Tuning::StandardB7.get_pitches();
+        assert_eq!(CagedShape::E.window(&seven_strings, Note::C), Ok((7, 4)));
+        assert!(CagedShape::E.window(&standard[1..], Note::C).is_err());
+    }
+
+    #[test]
+    fn test_build_caged_fret_board_open() {
+        let standard = Tuning::StandardE6.get_pitches();
+        for (root, shape) in [
+            (Note::C, CagedShape::C),
+            (Note::A, CagedShape::A),
+            (Note::G, CagedShape::G),
+            (Note::E, CagedShape::E),
+            (Note::D, CagedShape::D),
+        ] {
+            let notes = Scale::Major.get_notes(root);
+            let fret_board = build_caged_fret_board(&standard, root, shape, &notes).unwrap();
+            assert_eq!(fret_board.starting_fret, 0, "{} shape", shape);
+            let notes: HashSet<Note> = fret_board
+                .scale_positions()
+                .map(|(_, position)| position.note)
+                .collect();
+            assert_eq!(notes.len(), 7);
+            assert!(fret_board
+                .scale_positions()
+                .any(|(_, position)| position.fret == 0));
+            for string in &fret_board.strings {
+                let count = string.positions.iter().filter(|p| p.step.is_some()).count();
+                assert!((2..=3).contains(&count), "{} shape", shape);
+            }
+        }
+        // The open E shape of E ends on the second fret like the open chord.
+        assert_eq!(CagedShape::E.window(&standard, Note::E), Ok((0, 3)));
+    }
+
+    #[test]
+    fn test_build_caged_fret_board() {
+        let standard = Tuning::StandardE6.get_pitches();
+        let notes = Scale::Major.get_notes(Note::C);
+        for shape in CagedShape::iter() {
+            let fret_board = build_caged_fret_board(&standard, Note::C, shape, &notes).unwrap();
+            let pitches: Vec<Pitch> = fret_board
+                .scale_positions()
+                .map(|(_, position)| position.pitch)
+                .collect();
+            let unique: HashSet<&Pitch> = pitches.iter().collect();
+            assert_eq!(unique.len(), pitches.len());
+            let notes: HashSet<Note> = pitches.iter().map(|pitch| pitch.note).collect();
+            assert_eq!(notes.len(), 7);
+            // Two or three notes on every string.
+            for string in &fret_board.strings {
+                let count = string.positions.iter().filter(|p| p.step.is_some()).count();
+                assert!((2..=3).contains(&count), "{} shape", shape);
+            }
+        }
+
+        // The G shape of C covers frets 4 to 8, with the B on the G string's fourth fret.
+        let fret_board = build_caged_fret_board(&standard, Note::C, CagedShape::G, &notes).unwrap();
+        let g_string: Vec<usize> = fret_board.strings[3]
+            .positions
+            .iter()
+            .filter(|position| position.step.is_some())
+            .map(|position| position.fret)
+            .collect();
+        assert_eq!(g_string, vec![4, 5, 7]);
+        assert!(fits_caged(Scale::Mixolydian.get_steps()));
+        assert!(!fits_caged(Scale::Dorian.get_steps()));
+    }
+}
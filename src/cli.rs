@@ -1,182 +1,693 @@
-use chrono::{Datelike, Utc};
-use clap::Parser;
-use colored::Colorize;
-use rand::{rng, rngs::StdRng, seq::IndexedRandom, Rng, SeedableRng};
+use chrono::{Datelike, FixedOffset, Local, NaiveDate, Utc};
+use clap::{
+    builder::{PossibleValue, TypedValueParser},
+    error::ErrorKind,
+    parser::ValueSource,
+    Arg, ArgMatches, Command, CommandFactory, Error, FromArgMatches, Parser, Subcommand, ValueEnum,
+};
+use rand::{rng, RngCore};
+use std::{ffi::OsStr, path::PathBuf, str::FromStr};
 use strum::IntoEnumIterator;
 
-use crate::{
+use daily_scale::{
+    arpeggio::{chord_tones, fits_arpeggio},
+    caged::{fits_caged, CagedShape},
+    format::{format_with_color, Format, Labels},
     fret_board::{FRET_SPAN, NUM_FRETS},
-    notes::{get_flat_accidentals, Accidental, Note, NUM_NOTES},
-    scales::Scale,
-    tunings::Tuning,
+    notes::{Accidental, Note, NUM_NOTES},
+    pitch::A4_FREQUENCY,
+    playback::{NoteValue, DEFAULT_TEMPO},
+    random::{pick, SplitMix64},
+    scales::{parse_scales, Scale, ScaleSpec},
+    spelling::{spell_root, spell_scale, SpelledNote, Spelling},
+    three_nps::{three_nps_frets, three_nps_window},
+    tunings::{Tuning, TuningSpec},
+};
+
+use crate::{
+    config::{load_config, Config},
+    plan::{month_dates, parse_month, plan_dates, validate_plan_days, PlanDay, PlanFormat},
+    practice_log::{log_path, read_log, Candidates, LogEntry},
+    selection::{History, Schedule, Selection, Selector},
 };
 
-pub struct Format {
-    pub flat: bool,
-    pub colored: bool,
+const MIN_TEMPO: u32 = 20;
+const MAX_TEMPO: u32 = 300;
+const MAX_COUNT_IN: u32 = 16;
+const MIN_A4: f64 = 400.0;
+const MAX_A4: f64 = 480.0;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Svg,
+}
+
+/// How the frets of the practice window are chosen.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum System {
+    #[default]
+    Window,
+    Caged,
+    #[value(name = "3nps")]
+    ThreeNps,
+}
+
+/// A fingering pattern shown instead of every scale note in a window of frets.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Fingering {
+    Caged(CagedShape),
+    /// Three notes per string, starting on the lowest string at this degree of the scale.
+    ThreeNps(usize),
+}
+
+/// The time zone whose midnight starts a new scale of the day.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TimeZone {
+    #[default]
+    Utc,
+    Local,
+    Offset(FixedOffset),
+}
+
+impl TimeZone {
+    pub fn today(&self) -> NaiveDate {
+        let now = Utc::now();
+        match self {
+            TimeZone::Utc => now.date_naive(),
+            TimeZone::Local => now.with_timezone(&Local).date_naive(),
+            TimeZone::Offset(offset) => now.with_timezone(offset).date_naive(),
+        }
+    }
+}
+
+impl FromStr for TimeZone {
+    type Err = String;
+
+    /// Parses `utc`, `local` or a fixed offset from UTC such as `+09:00` or `-05:30`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "utc" => Ok(TimeZone::Utc),
+            "local" => Ok(TimeZone::Local),
+            _ => s.parse::<FixedOffset>().map(TimeZone::Offset).map_err(|_| {
+                format!(
+                    "'{}' is not utc, local or an offset from UTC such as +09:00",
+                    s
+                )
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Subcommand)]
+pub enum Action {
+    #[command(about = "Record today's scale in the practice log")]
+    Done {
+        #[arg(
+            long,
+            value_name = "BPM",
+            value_parser = |s: &str| {
+                let num = s.parse::<u32>().map_err(|_| "Not a valid number")?;
+                validate_tempo(num)
+            },
+            help = "Tempo you practiced the scale at"
+        )]
+        tempo: Option<u32>,
+
+        #[arg(long, help = "Notes about the practice session")]
+        notes: Option<String>,
+    },
+    #[command(about = "Rate how well today's scale went, to schedule when it comes up again")]
+    Rate {
+        #[arg(
+            value_parser = clap::value_parser!(u8).range(1..=5),
+            help = "From 1 (couldn't play it) to 5 (effortless)"
+        )]
+        grade: u8,
+    },
+    #[command(about = "Print the scales of the upcoming days as a practice plan")]
+    Plan {
+        #[arg(
+            long,
+            default_value_t = 7,
+            conflicts_with = "month",
+            value_parser = |s: &str| {
+                let num = s.parse::<u32>().map_err(|_| "Not a valid number")?;
+                validate_plan_days(num)
+            },
+            help = "Number of days to plan, starting today"
+        )]
+        days: u32,
+
+        #[arg(
+            long,
+            value_name = "YYYY-MM",
+            value_parser = parse_month,
+            help = "Plan every day of this month instead"
+        )]
+        month: Option<NaiveDate>,
+
+        #[arg(
+            value_enum,
+            long,
+            default_value = "text",
+            help = "Select how the plan is printed"
+        )]
+        format: PlanFormat,
+    },
+    #[command(about = "List every root and scale containing the given notes, exact matches first")]
+    Identify {
+        #[arg(
+            required = true,
+            value_delimiter = ',',
+            value_parser = |s: &str| s.parse::<SpelledNote>(),
+            help = "Comma separated notes to look up (e.g. C,E,G,Bb,D)"
+        )]
+        notes: Vec<SpelledNote>,
+    },
+    #[command(
+        about = "Draw two scales on the same root over one fret window and show how they differ"
+    )]
+    Compare {
+        #[arg(
+            value_parser = ScaleParser,
+            help = "The first scale, whose own notes are drawn in (parentheses), written like a scale of --scales"
+        )]
+        first: ScaleSpec,
+
+        #[arg(
+            value_parser = ScaleParser,
+            help = "The second scale, whose own notes are drawn in [brackets], written like a scale of --scales"
+        )]
+        second: ScaleSpec,
+
+        #[arg(
+            value_enum,
+            long,
+            ignore_case = true,
+            help = "Root note of both scales instead of the root of the day"
+        )]
+        root: Option<Accidental>,
+    },
+    #[command(
+        about = "Show practice streaks and the most and least practiced scales, roots and tunings"
+    )]
+    Stats,
 }
 
 pub struct Params {
-    pub tuning: Tuning,
+    /// The day the scale was picked for.
+    pub date: NaiveDate,
+    pub action: Option<Action>,
+    /// The scales of the days to plan for the `plan` command.
+    pub plan: Vec<PlanDay>,
+    pub log_file: Option<PathBuf>,
+    /// Everything the scale of the day was picked from, for the stats.
+    pub candidates: Candidates,
+    pub tuning: TuningSpec,
     pub root_note: Note,
-    pub scale: Scale,
+    pub scale: ScaleSpec,
     pub starting_fret: usize,
+    pub fret_span: usize,
+    /// The fingering shown instead of every scale note in a window of frets.
+    pub fingering: Option<Fingering>,
     pub notes_in_scale: Vec<(Note, usize)>,
+    /// The degree of the chord whose arpeggio is highlighted.
+    pub arpeggio: Option<usize>,
     pub format: Format,
+    pub output: OutputFormat,
+    pub out_file: Option<PathBuf>,
+    pub midi: Option<PathBuf>,
+    pub tempo: u32,
+    pub note_value: NoteValue,
+    pub wav: Option<PathBuf>,
+    pub count_in: u32,
+    pub a4: f64,
+    pub tab: bool,
+    /// The number of questions of the quiz, if one was asked for.
+    pub quiz: Option<usize>,
+    pub tab_root_string: Option<usize>,
+    pub repeat_top: bool,
 }
 
 pub fn get_params() -> Params {
-    let args = Args::parse();
+    let matches = Args::command().get_matches();
+    let mut args = Args::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
+    match load_config(args.config.as_deref(), args.profile.as_deref()) {
+        Ok(config) => apply_config(&mut args, &matches, config),
+        Err(error) => Args::command().error(ErrorKind::InvalidValue, error).exit(),
+    }
+    if let Some(ref starting_frets) = args.starting_frets {
+        if let Err(error) = starting_frets
+            .iter()
+            .try_for_each(|fret| validate_starting_fret(*fret, args.span).map(|_| ()))
+        {
+            Args::command()
+                .error(
+                    ErrorKind::ValueValidation,
+                    format!(
+                        "invalid starting fret for a span of {}: {}",
+                        args.span, error
+                    ),
+                )
+                .exit();
+        }
+    }
+
+    let date = args.date.unwrap_or_else(|| args.timezone.today());
+    let entries = match args.selection {
+        Selection::Random => Vec::new(),
+        Selection::History | Selection::Spaced => {
+            match log_path(args.log_file.as_deref()).and_then(|path| read_log(&path)) {
+                Ok(entries) => entries,
+                Err(error) => Args::command().error(ErrorKind::Io, error).exit(),
+            }
+        }
+    };
+    let plan = match args.action {
+        Some(Action::Plan { days, month, .. }) => {
+            let dates = match month {
+                Some(first) => month_dates(first),
+                None => plan_dates(date, days),
+            };
+            dates
+                .into_iter()
+                .map(|date| PlanDay::from(&pick_for(date, &entries, args.clone())))
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+    Params {
+        plan,
+        ..pick_for(date, &entries, args)
+    }
+}
 
-    if args.full_randomness {
-        get_params_impl(&mut rng(), args)
+/// Picks the scale of `date`, the same way for today as for any day of a plan.
+fn pick_for(date: NaiveDate, entries: &[LogEntry], args: Args) -> Params {
+    if args.full_randomness && args.seed.is_none() {
+        get_params_impl(&mut rng(), date, entries, args)
     } else {
-        let seed = Utc::now().date_naive().num_days_from_ce() as u64;
-        get_params_impl(&mut StdRng::seed_from_u64(seed), args)
+        let seed = args.seed.unwrap_or_else(|| daily_seed(date));
+        get_params_impl(&mut SplitMix64::new(seed), date, entries, args)
+    }
+}
+
+/// Fills in every option that was not given on the command line from the configuration file.
+fn apply_config(args: &mut Args, matches: &ArgMatches, config: Config) {
+    let from_command_line = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+    let Config {
+        tuning,
+        scales,
+        root_notes,
+        starting_frets,
+        full_randomness,
+        uncolored,
+        labels,
+        output,
+        span,
+        full_neck,
+        system,
+        position,
+        start_degree,
+        arpeggio,
+        tab,
+        tab_root_string,
+        repeat_top,
+        tempo,
+        note_value,
+        count_in,
+        a4,
+        log_file,
+        selection,
+        timezone,
+    } = config;
+
+    if !from_command_line("tuning") && tuning.is_some() {
+        args.tuning = tuning;
+    }
+    if !from_command_line("scales") && scales.is_some() {
+        args.scales = scales;
+    }
+    if !from_command_line("root_notes") && root_notes.is_some() {
+        args.root_notes = root_notes;
+    }
+    if !from_command_line("starting_frets") && starting_frets.is_some() {
+        args.starting_frets = starting_frets;
+    }
+    if !from_command_line("full_randomness") {
+        args.full_randomness = full_randomness.unwrap_or(args.full_randomness);
+    }
+    if !from_command_line("uncolored") {
+        args.uncolored = uncolored.unwrap_or(args.uncolored);
+    }
+    if !from_command_line("labels") {
+        args.labels = labels.unwrap_or(args.labels);
+    }
+    if !from_command_line("output") {
+        args.output = output.unwrap_or(args.output);
+    }
+    if !from_command_line("span") {
+        args.span = span.unwrap_or(args.span);
+    }
+    if !["full_neck", "span", "starting_frets"]
+        .into_iter()
+        .any(from_command_line)
+    {
+        args.full_neck = full_neck.unwrap_or(args.full_neck);
+    }
+    if !["system", "full_neck", "starting_frets"]
+        .into_iter()
+        .any(from_command_line)
+    {
+        args.system = system.unwrap_or(args.system);
+    }
+    if !from_command_line("position") && position.is_some() {
+        args.position = position;
+    }
+    if !from_command_line("start_degree") && start_degree.is_some() {
+        args.start_degree = start_degree;
+    }
+    if !from_command_line("arpeggio") && arpeggio.is_some() {
+        args.arpeggio = arpeggio;
+    }
+    if !from_command_line("tab") {
+        args.tab = tab.unwrap_or(args.tab);
+    }
+    if !from_command_line("tab_root_string") && tab_root_string.is_some() {
+        args.tab_root_string = tab_root_string;
+    }
+    if !from_command_line("repeat_top") {
+        args.repeat_top = repeat_top.unwrap_or(args.repeat_top);
+    }
+    if !from_command_line("tempo") {
+        args.tempo = tempo.unwrap_or(args.tempo);
+    }
+    if !from_command_line("note_value") {
+        args.note_value = note_value.unwrap_or(args.note_value);
+    }
+    if !from_command_line("count_in") {
+        args.count_in = count_in.unwrap_or(args.count_in);
+    }
+    if !from_command_line("a4") {
+        args.a4 = a4.unwrap_or(args.a4);
+    }
+    if !from_command_line("log_file") && log_file.is_some() {
+        args.log_file = log_file;
+    }
+    if !from_command_line("selection") {
+        args.selection = selection.unwrap_or(args.selection);
+    }
+    if !from_command_line("timezone") {
+        args.timezone = timezone.unwrap_or(args.timezone);
     }
 }
 
-fn get_params_impl<R: Rng + ?Sized>(rng: &mut R, args: Args) -> Params {
+/// The seed of the scale of the day, which must never change for a given date.
+fn daily_seed(date: NaiveDate) -> u64 {
+    date.num_days_from_ce() as u64
+}
+
+fn get_params_impl<R: RngCore + ?Sized>(
+    rng: &mut R,
+    date: NaiveDate,
+    entries: &[LogEntry],
+    args: Args,
+) -> Params {
     let Args {
         tuning,
         root_notes,
         scales,
         starting_frets,
         uncolored,
+        labels,
+        output,
+        out_file,
+        span,
+        full_neck,
+        midi,
+        tempo,
+        note_value,
+        wav,
+        count_in,
+        a4,
+        action,
+        log_file,
+        selection,
+        system,
+        position,
+        start_degree,
+        arpeggio,
+        tab,
+        tab_root_string,
+        repeat_top,
+        quiz,
         ..
     } = args;
 
-    let notes = Note::iter().collect::<Vec<Note>>();
-    let flat_accidentals = get_flat_accidentals();
-
-    let tuning = tuning.unwrap();
+    let tuning = tuning.unwrap_or(TuningSpec::Preset(Tuning::StandardE6));
+    let selector = match selection {
+        Selection::Random => None,
+        Selection::History => Some(Selector::History(History::new(entries, date))),
+        Selection::Spaced => Some(Selector::Schedule(Schedule::new(
+            entries,
+            &tuning.key(),
+            date,
+        ))),
+    };
 
-    let mut flat = false;
-    let root_note = if let Some(ref arg_notes) = root_notes {
-        let arg_note = arg_notes.choose(rng).unwrap();
-        if flat_accidentals.contains(arg_note) {
-            flat = true
-        }
-        arg_note.to_note()
-    } else {
-        notes.choose(rng).copied().unwrap()
+    let roots: Vec<(Note, Option<Accidental>)> = match root_notes {
+        Some(arg_notes) => arg_notes
+            .into_iter()
+            .map(|arg_note| (arg_note.to_note(), Some(arg_note)))
+            .collect(),
+        None => Note::iter().map(|note| (note, None)).collect(),
     };
-    let format = Format {
-        flat,
-        colored: !uncolored,
+    let mut scales: Vec<ScaleSpec> =
+        scales.unwrap_or_else(|| Scale::iter().map(ScaleSpec::from).collect());
+    if system == System::Caged {
+        scales.retain(|scale| fits_caged(scale.get_steps()));
+        if scales.is_empty() {
+            Args::command()
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    "the CAGED system needs a scale with a major third and a perfect fifth",
+                )
+                .exit();
+        }
+    }
+    if arpeggio.is_some() {
+        scales.retain(|scale| fits_arpeggio(scale.get_steps()));
+        if scales.is_empty() {
+            Args::command()
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    "arpeggios need a scale with at least seven notes",
+                )
+                .exit();
+        }
+    }
+    let ((root_note, arg_note), scale) = match selector {
+        Some(selector) => {
+            let combinations: Vec<((Note, Option<Accidental>), &ScaleSpec)> = roots
+                .iter()
+                .flat_map(|root| scales.iter().map(move |scale| (*root, scale)))
+                .collect();
+            let (root, scale) =
+                selector.choose(rng, &combinations, |((root, _), scale)| (*root, *scale));
+            (*root, (*scale).clone())
+        }
+        None => {
+            let root = *pick(rng, &roots, |(note, arg_note)| match arg_note {
+                Some(arg_note) => arg_note.to_possible_value().unwrap().get_name().to_string(),
+                None => String::from(note.to_str(false)),
+            })
+            .unwrap();
+            (root, pick(rng, &scales, ScaleSpec::key).cloned().unwrap())
+        }
     };
 
-    let scale = if let Some(ref arg_scales) = scales {
-        arg_scales.choose(rng).copied().unwrap()
-    } else {
-        Scale::iter()
-            .collect::<Vec<Scale>>()
-            .choose(rng)
-            .copied()
-            .unwrap()
+    let candidates = Candidates {
+        scales: scales.iter().map(ScaleSpec::key).collect(),
+        roots: roots
+            .iter()
+            .map(|(note, _)| String::from(note.to_str(false)))
+            .collect(),
+        tunings: vec![tuning.key()],
     };
 
-    let all_frets: Vec<usize> = (0..=NUM_FRETS - FRET_SPAN).collect();
-    let starting_fret = if let Some(ref arg_frets) = starting_frets {
-        arg_frets.choose(rng).copied().unwrap()
-    } else {
-        all_frets.choose(rng).copied().unwrap()
+    let notes_in_scale = scale.get_notes(root_note);
+    if let Some(Err(error)) = arpeggio.map(|degree| chord_tones(&notes_in_scale, degree)) {
+        Args::command().error(ErrorKind::InvalidValue, error).exit();
+    }
+
+    let pitches = tuning.get_pitches();
+    let fingering = match system {
+        System::Window => None,
+        System::Caged => Some(Fingering::Caged(position.unwrap_or_else(|| {
+            let shapes: Vec<CagedShape> = CagedShape::iter().collect();
+            *pick(rng, &shapes, CagedShape::to_string).unwrap()
+        }))),
+        System::ThreeNps => Some(Fingering::ThreeNps(start_degree.unwrap_or_else(|| {
+            // Only the degrees whose pattern fits on the neck.
+            let degrees: Vec<usize> = (1..=notes_in_scale.len())
+                .filter(|degree| three_nps_frets(&pitches, &notes_in_scale, *degree).is_ok())
+                .collect();
+            pick(rng, &degrees, usize::to_string).copied().unwrap_or(1)
+        }))),
+    };
+    let window = match fingering {
+        Some(Fingering::Caged(shape)) => Some(shape.window(&pitches, root_note)),
+        Some(Fingering::ThreeNps(degree)) => {
+            Some(three_nps_window(&pitches, &notes_in_scale, degree))
+        }
+        None => None,
+    };
+    let (starting_fret, fret_span) = match window {
+        Some(Ok(window)) => window,
+        Some(Err(error)) => Args::command().error(ErrorKind::InvalidValue, error).exit(),
+        None => {
+            let fret_span = if full_neck { NUM_FRETS + 1 } else { span };
+            let all_frets: Vec<usize> = (0..=NUM_FRETS.saturating_sub(fret_span)).collect();
+            let starting_fret = if full_neck {
+                0
+            } else if let Some(ref arg_frets) = starting_frets {
+                *pick(rng, arg_frets, usize::to_string).unwrap()
+            } else {
+                *pick(rng, &all_frets, usize::to_string).unwrap()
+            };
+            (starting_fret, fret_span)
+        }
     };
 
-    let root_note_index = notes.iter().position(|&note| note == root_note).unwrap();
-    let steps = scale.get_steps();
-    let notes_in_scale = steps
-        .iter()
-        .map(|step| {
-            let note_index = (root_note_index + *step) % NUM_NOTES;
-            (notes[note_index], *step)
-        })
-        .collect::<Vec<(Note, usize)>>();
+    let degrees = scale.get_degrees();
+    let spelled_root = arg_note
+        .map(SpelledNote::from)
+        .unwrap_or_else(|| spell_root(root_note, &degrees));
+    let format = Format {
+        spelling: Spelling::Scale(spell_scale(spelled_root, &degrees)),
+        colored: !uncolored,
+        labels,
+        degrees: degrees.clone(),
+    };
 
     Params {
+        date,
+        action,
+        plan: Vec::new(),
+        log_file,
+        candidates,
         tuning,
         root_note,
         scale,
         starting_fret,
+        fret_span,
+        fingering,
         notes_in_scale,
+        arpeggio,
         format,
+        output,
+        out_file,
+        midi,
+        tempo,
+        note_value,
+        wav,
+        count_in,
+        a4,
+        tab,
+        quiz,
+        tab_root_string,
+        repeat_top,
     }
 }
 
-pub fn print_output(params: Params, fret_board: Vec<String>) {
+pub fn text_output(params: &Params, fret_board: Vec<String>) -> String {
     let Params {
-        tuning,
+        ref tuning,
         root_note,
-        scale,
+        ref scale,
         starting_fret,
+        fingering,
         ref format,
         ref notes_in_scale,
+        arpeggio,
+        ..
+    } = *params;
+    let Format {
+        ref spelling,
+        colored,
         ..
-    } = params;
-    let Format { flat, colored } = *format;
+    } = *format;
 
+    let mut output = String::new();
     for string in fret_board {
-        println!("{}", string);
+        output.push_str(&string);
+        output.push('\n');
     }
 
-    println!(
-        "Here's the scale of the day: {} {} starting at fret {} in {} tuning",
-        format_with_color(root_note.to_str(flat), 0, colored),
+    output.push_str(&format!(
+        "Here's the scale of the day: {} {} {} fret {} in {} tuning\n",
+        format_with_color(&spelling.name(root_note), 0, colored),
         scale,
+        match fingering {
+            Some(Fingering::Caged(shape)) => format!("in the {} shape from", shape),
+            Some(Fingering::ThreeNps(degree)) =>
+                format!("in three notes per string from degree {} at", degree),
+            None => String::from("starting at"),
+        },
         starting_fret,
         tuning,
-    );
+    ));
 
-    println!(
-        "The notes in this scale are: {}",
+    output.push_str(&format!(
+        "The notes in this scale are: {}\n",
         notes_in_scale
             .iter()
-            .map(|(note, step)| format_with_color(note.to_str(flat), *step, colored))
+            .map(|(note, step)| format_with_color(&spelling.name(*note), *step, colored))
             .collect::<Vec<String>>()
             .join(", ")
-    );
-}
+    ));
 
-pub fn format_with_color(note_string: &str, step: usize, colored: bool) -> String {
-    if !colored {
-        return String::from(note_string);
-    }
-    match step {
-        0 => format!("{}", note_string.green()),
-        3 => format!("{}", note_string.red()),
-        4 => format!("{}", note_string.red()),
-        5 => format!("{}", note_string.cyan()),
-        6 => format!("{}", note_string.black()),
-        7 => format!("{}", note_string.blue()),
-        9 => format!("{}", note_string.magenta()),
-        10 => format!("{}", note_string.yellow()),
-        11 => format!("{}", note_string.yellow()),
-        _ => String::from(note_string),
+    if let Some(degree) = arpeggio {
+        output.push_str(&format!(
+            "The chord tones of degree {} are: {}\n",
+            degree,
+            chord_tones(notes_in_scale, degree)
+                .unwrap_or_default()
+                .iter()
+                .map(|(note, step)| format_with_color(&spelling.name(*note), *step, colored))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ));
     }
+    output
 }
 
-#[derive(Parser, Debug)]
+#[derive(Parser, Clone, Debug)]
 #[command(name = "daily-scale", version, about = "Have you practiced today?", long_about = None)]
 struct Args {
     #[arg(
-        value_enum,
         required = false,
         short = 't',
         long,
         default_value = "standard-e6",
-        help = "Select the tuning you want to play in"
+        value_parser = TuningSpecParser,
+        help = "Select the tuning you want to play in, or provide comma separated open string notes from low to high (e.g. D,A,D,G,A,D or D2,A2,D3,G3,A3,D4)"
     )]
-    tuning: Option<Tuning>,
+    tuning: Option<TuningSpec>,
 
     #[arg(
-        value_enum,
-        value_delimiter = ',',
         required = false,
         short = 's',
         long,
-        help = "Provide a comma separated list of scales"
+        value_parser = ScaleListParser,
+        help = "Provide a comma separated list of scales, where custom scales are written as semitone steps (e.g. 0,2,3,6,7,8,11) or degrees (e.g. 1,2,b3,#4,5,b6,7)"
     )]
-    scales: Option<Vec<Scale>>,
+    // The whole list is parsed at once, as custom scales contain commas themselves. Spelling out
+    // the path keeps clap from taking `Vec` for a list of single values.
+    scales: Option<::std::vec::Vec<ScaleSpec>>,
 
     #[arg(
         value_enum,
@@ -196,11 +707,7 @@ struct Args {
         long,
         value_parser = |s: &str| {
             let num = s.parse::<usize>().map_err(|_| "Not a valid number")?;
-            if num <= (NUM_FRETS - FRET_SPAN + 1) {
-                Ok(num)
-            } else {
-                Err(format!("Number must be <= {}", NUM_FRETS - FRET_SPAN + 1))
-            }
+            validate_starting_fret(num, 1)
         },
         help = "Provide a comma separated list of numbers for the starting fret"
     )]
@@ -214,6 +721,42 @@ struct Args {
     )]
     full_randomness: bool,
 
+    #[arg(
+        required = false,
+        long,
+        value_name = "YYYY-MM-DD",
+        value_parser = |s: &str| s.parse::<NaiveDate>().map_err(|_| "Not a valid date"),
+        help = "Pick the scale of this day instead of today's"
+    )]
+    date: Option<NaiveDate>,
+
+    #[arg(
+        required = false,
+        long,
+        conflicts_with = "full_randomness",
+        help = "Seed the scale generator with this number instead of the date"
+    )]
+    seed: Option<u64>,
+
+    #[arg(
+        required = false,
+        long,
+        value_name = "ZONE",
+        allow_hyphen_values = true,
+        default_value = "utc",
+        help = "Time zone whose midnight starts a new day: utc, local or an offset such as +09:00"
+    )]
+    timezone: TimeZone,
+
+    #[arg(
+        value_enum,
+        required = false,
+        long,
+        default_value = "random",
+        help = "Select how the root and scale are picked, where history prefers the ones you haven't practiced recently and spaced the ones due for review"
+    )]
+    selection: Selection,
+
     #[arg(
         required = false,
         short = 'c',
@@ -221,5 +764,465 @@ struct Args {
         help = "If enabled, the output will be in plain text without color"
     )]
     uncolored: bool,
+
+    #[arg(
+        value_enum,
+        required = false,
+        long,
+        default_value = "notes",
+        help = "Select what the notes on the fret board are labeled with, where fingers counts one finger per fret from the lowest fretted note"
+    )]
+    labels: Labels,
+
+    #[arg(
+        value_enum,
+        required = false,
+        short = 'o',
+        long,
+        default_value = "text",
+        help = "Select how the scale is printed"
+    )]
+    output: OutputFormat,
+
+    #[arg(
+        required = false,
+        long,
+        value_name = "QUESTIONS",
+        num_args = 0..=1,
+        default_missing_value = "5",
+        conflicts_with_all = ["output", "out_file"],
+        value_parser = clap::value_parser!(u8).range(1..=100).map(usize::from),
+        help = "Quiz yourself on the fret board with only the roots shown, naming the notes at this many positions [default: 5]"
+    )]
+    quiz: Option<usize>,
+
+    #[arg(
+        required = false,
+        long,
+        value_name = "FILE",
+        help = "Write the output to this file instead of printing it"
+    )]
+    out_file: Option<PathBuf>,
+
+    #[arg(
+        required = false,
+        long,
+        default_value_t = FRET_SPAN,
+        value_parser = |s: &str| {
+            let num = s.parse::<usize>().map_err(|_| "Not a valid number")?;
+            if (1..=NUM_FRETS + 1).contains(&num) {
+                Ok(num)
+            } else {
+                Err(format!("Number must be between 1 and {}", NUM_FRETS + 1))
+            }
+        },
+        help = "Number of frets to show"
+    )]
+    span: usize,
+
+    #[arg(
+        required = false,
+        long,
+        conflicts_with_all = ["span", "starting_frets"],
+        help = "If enabled, every fret from 0 to 24 is shown"
+    )]
+    full_neck: bool,
+
+    #[arg(
+        value_enum,
+        required = false,
+        long,
+        default_value = "window",
+        conflicts_with_all = ["starting_frets", "full_neck"],
+        visible_alias = "pattern",
+        help = "Select how the frets are chosen, where window picks a random starting fret, caged one of the five CAGED shapes around the root and 3nps three notes per string"
+    )]
+    system: System,
+
+    #[arg(
+        value_enum,
+        required = false,
+        long,
+        value_name = "SHAPE",
+        ignore_case = true,
+        help = "Show this CAGED shape instead of a random one when using --system caged"
+    )]
+    position: Option<CagedShape>,
+
+    #[arg(
+        required = false,
+        long,
+        value_name = "DEGREE",
+        value_parser = clap::value_parser!(u8).range(1..=NUM_NOTES as i64).map(usize::from),
+        help = "Start the pattern on this degree of the scale instead of a random one when using --system 3nps"
+    )]
+    start_degree: Option<usize>,
+
+    #[arg(
+        required = false,
+        long,
+        value_name = "DEGREE",
+        value_parser = clap::value_parser!(u8).range(1..=NUM_NOTES as i64).map(usize::from),
+        help = "Highlight the chord tones of the seventh chord on this degree of the scale and dim the other notes"
+    )]
+    arpeggio: Option<usize>,
+
+    #[arg(
+        required = false,
+        long,
+        help = "If enabled, the text output also shows the scale as tab, played up and back down"
+    )]
+    tab: bool,
+
+    #[arg(
+        required = false,
+        long,
+        value_name = "STRING",
+        value_parser = clap::value_parser!(u64).range(1..).map(|string| string as usize),
+        help = "Start the tab on the root on this string, counted from the highest, instead of the lowest note"
+    )]
+    tab_root_string: Option<usize>,
+
+    #[arg(
+        required = false,
+        long,
+        help = "If enabled, the top note of the tab is played twice"
+    )]
+    repeat_top: bool,
+
+    #[arg(
+        required = false,
+        long,
+        value_name = "FILE",
+        help = "Also write a MIDI file that plays the scale up and down the fret window"
+    )]
+    midi: Option<PathBuf>,
+
+    #[arg(
+        required = false,
+        long,
+        value_name = "BPM",
+        default_value_t = DEFAULT_TEMPO,
+        value_parser = |s: &str| {
+            let num = s.parse::<u32>().map_err(|_| "Not a valid number")?;
+            validate_tempo(num)
+        },
+        help = "Tempo of the MIDI and WAV files in beats per minute"
+    )]
+    tempo: u32,
+
+    #[arg(
+        value_enum,
+        required = false,
+        long,
+        default_value = "quarter",
+        help = "Length of every note in the MIDI and WAV files"
+    )]
+    note_value: NoteValue,
+
+    #[arg(
+        required = false,
+        long,
+        value_name = "FILE",
+        help = "Also write a WAV file of the scale played up and down on a synthesized guitar"
+    )]
+    wav: Option<PathBuf>,
+
+    #[arg(
+        required = false,
+        long,
+        value_name = "BEATS",
+        default_value_t = 0,
+        value_parser = |s: &str| {
+            let num = s.parse::<u32>().map_err(|_| "Not a valid number")?;
+            validate_count_in(num)
+        },
+        help = "Number of metronome clicks before the scale starts in the WAV file"
+    )]
+    count_in: u32,
+
+    #[arg(
+        required = false,
+        long,
+        value_name = "HZ",
+        default_value_t = A4_FREQUENCY,
+        value_parser = |s: &str| {
+            let num = s.parse::<f64>().map_err(|_| "Not a valid number")?;
+            validate_a4(num)
+        },
+        help = "Frequency of A4 the WAV file is tuned to"
+    )]
+    a4: f64,
+
+    #[arg(
+        required = false,
+        long,
+        value_name = "FILE",
+        help = "Keep the practice log in this file instead of $XDG_DATA_HOME/daily-scale/log.jsonl"
+    )]
+    log_file: Option<PathBuf>,
+
+    #[arg(
+        required = false,
+        long,
+        value_name = "FILE",
+        help = "Read default options from this file instead of $XDG_CONFIG_HOME/daily-scale/config.toml"
+    )]
+    config: Option<PathBuf>,
+
+    #[arg(
+        required = false,
+        short = 'p',
+        long,
+        help = "Use the options of this profile from the configuration file"
+    )]
+    profile: Option<String>,
+
+    #[command(subcommand)]
+    action: Option<Action>,
+}
+
+pub fn validate_starting_fret(num: usize, span: usize) -> Result<usize, String> {
+    if num + span <= NUM_FRETS + 1 {
+        Ok(num)
+    } else {
+        Err(format!("Number must be <= {}", NUM_FRETS + 1 - span))
+    }
+}
+
+pub fn validate_tempo(num: u32) -> Result<u32, String> {
+    if (MIN_TEMPO..=MAX_TEMPO).contains(&num) {
+        Ok(num)
+    } else {
+        Err(format!(
+            "Number must be between {} and {}",
+            MIN_TEMPO, MAX_TEMPO
+        ))
+    }
+}
+
+pub fn validate_count_in(num: u32) -> Result<u32, String> {
+    if num <= MAX_COUNT_IN {
+        Ok(num)
+    } else {
+        Err(format!("Number must be <= {}", MAX_COUNT_IN))
+    }
+}
+
+pub fn validate_a4(num: f64) -> Result<f64, String> {
+    if (MIN_A4..=MAX_A4).contains(&num) {
+        Ok(num)
+    } else {
+        Err(format!("Number must be between {} and {}", MIN_A4, MAX_A4))
+    }
 }
 
+#[derive(Clone)]
+struct TuningSpecParser;
+
+impl TypedValueParser for TuningSpecParser {
+    type Value = TuningSpec;
+
+    fn parse_ref(
+        &self,
+        _cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, Error> {
+        let value = value
+            .to_str()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidUtf8))?;
+        value
+            .parse::<TuningSpec>()
+            .map_err(|error| invalid_value(arg, value, error))
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(
+            Tuning::value_variants()
+                .iter()
+                .filter_map(ValueEnum::to_possible_value),
+        ))
+    }
+}
+
+#[derive(Clone)]
+struct ScaleListParser;
+
+impl TypedValueParser for ScaleListParser {
+    type Value = Vec<ScaleSpec>;
+
+    fn parse_ref(
+        &self,
+        _cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, Error> {
+        let value = value
+            .to_str()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidUtf8))?;
+        parse_scales(value).map_err(|error| invalid_value(arg, value, error))
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(
+            Scale::value_variants()
+                .iter()
+                .filter_map(ValueEnum::to_possible_value),
+        ))
+    }
+}
+
+/// Parses a single scale, written like one entry of [`ScaleListParser`].
+#[derive(Clone)]
+struct ScaleParser;
+
+impl TypedValueParser for ScaleParser {
+    type Value = ScaleSpec;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, Error> {
+        let mut scales = ScaleListParser.parse_ref(cmd, arg, value)?;
+        if scales.len() == 1 {
+            Ok(scales.remove(0))
+        } else {
+            let value = value.to_string_lossy();
+            Err(invalid_value(
+                arg,
+                &value,
+                format!("expected one scale, found {}", scales.len()),
+            ))
+        }
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        ScaleListParser.possible_values()
+    }
+}
+
+fn invalid_value(arg: Option<&Arg>, value: &str, error: String) -> Error {
+    let arg = arg.map(|arg| arg.to_string()).unwrap_or_default();
+    Error::raw(
+        ErrorKind::InvalidValue,
+        format!("invalid value '{}' for '{}': {}\n", value, arg, error),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn daily_pick(date: &str, options: &[&str]) -> (Note, String, usize) {
+        let date: NaiveDate = date.parse().unwrap();
+        let args = Args::try_parse_from([&["daily-scale"], options].concat()).unwrap();
+        let params = get_params_impl(&mut SplitMix64::new(daily_seed(date)), date, &[], args);
+        (params.root_note, params.scale.key(), params.starting_fret)
+    }
+
+    #[test]
+    fn test_daily_pick() {
+        // Changing any of these changes the scale of the day for everyone.
+        let expected = [
+            ("2026-10-18", Note::F, "pentatonic-major", 7),
+            ("2026-10-19", Note::CSharp, "pentatonic-neutral", 5),
+            ("2027-01-01", Note::D, "dorian", 7),
+            ("2030-06-15", Note::CSharp, "pentatonic-neutral", 3),
+        ];
+        for (date, root, scale, fret) in expected {
+            assert_eq!(daily_pick(date, &[]), (root, String::from(scale), fret));
+        }
+
+        let options = ["-s", "dorian,lydian,major", "-n", "a,c,e", "-f", "0,5,12"];
+        let expected = [
+            ("2026-10-18", Note::E, "dorian", 12),
+            ("2026-10-19", Note::E, "major", 5),
+            ("2027-01-01", Note::E, "dorian", 0),
+            ("2030-06-15", Note::C, "major", 0),
+        ];
+        for (date, root, scale, fret) in expected {
+            assert_eq!(
+                daily_pick(date, &options),
+                (root, String::from(scale), fret)
+            );
+        }
+        // The order of the options doesn't matter.
+        let reordered = ["-s", "major,lydian,dorian", "-n", "e,c,a", "-f", "12,0,5"];
+        assert_eq!(
+            daily_pick("2026-10-18", &reordered),
+            (Note::E, String::from("dorian"), 12)
+        );
+    }
+
+    #[test]
+    fn test_tab_root_string() {
+        let thirteen_strings = "e,a,d,g,b,e,a,d,g,b,e,a,d";
+        let args = Args::try_parse_from([
+            "daily-scale",
+            "-t",
+            thirteen_strings,
+            "--tab-root-string",
+            "13",
+        ])
+        .unwrap();
+        assert_eq!(args.tab_root_string, Some(13));
+        assert!(Args::try_parse_from(["daily-scale", "--tab-root-string", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_compare_scales() {
+        let scales = |options: &[&str]| {
+            let args = Args::try_parse_from([&["daily-scale", "compare"], options].concat())?;
+            Ok::<_, Error>(match args.action {
+                Some(Action::Compare { first, second, .. }) => (first.key(), second.key()),
+                _ => Default::default(),
+            })
+        };
+        assert_eq!(
+            scales(&["1,2,b3,5,6", "dorian"]).unwrap(),
+            (String::from("1,2,b3,5,6"), String::from("dorian"))
+        );
+        assert_eq!(
+            scales(&["aeolian", "0,2,3,5,7,8,11"]).unwrap(),
+            (String::from("aeolian"), String::from("0,2,3,5,7,8,11"))
+        );
+        assert!(scales(&["dorian,lydian", "aeolian"]).is_err());
+        assert!(scales(&["dorian", "1,2,2"]).is_err());
+    }
+
+    #[test]
+    fn test_compare_root() {
+        let root = |name: &str| {
+            let args = Args::try_parse_from([
+                "daily-scale",
+                "compare",
+                "dorian",
+                "aeolian",
+                "--root",
+                name,
+            ])
+            .unwrap();
+            match args.action {
+                Some(Action::Compare { root, .. }) => root,
+                _ => None,
+            }
+        };
+        assert_eq!(root("A"), Some(Accidental::A));
+        assert_eq!(root("a"), Some(Accidental::A));
+        assert_eq!(root("Bb"), Some(Accidental::BFlat));
+        assert_eq!(root("F#"), Some(Accidental::FSharp));
+        assert_eq!(root("b-flat"), Some(Accidental::BFlat));
+        assert!(Args::try_parse_from([
+            "daily-scale",
+            "compare",
+            "dorian",
+            "aeolian",
+            "--root",
+            "H"
+        ])
+        .is_err());
+    }
+}
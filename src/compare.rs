@@ -0,0 +1,160 @@
+//! Two scales on the same root drawn over one window of frets, to see where they differ.
+
+use crate::{
+    fret_board::{build_fret_board, FretBoard, Highlight},
+    notes::Note,
+    pitch::Pitch,
+    scales::{Degree, ScaleSpec, MAJOR_STEPS},
+};
+
+/// Lays out the notes of both scales built on `root`. Notes only in `first` are highlighted as
+/// [`Highlight::First`], notes only in `second` as [`Highlight::Second`].
+pub fn build_comparison_fret_board(
+    tuning: &[Pitch],
+    starting_fret: usize,
+    fret_span: usize,
+    root: Note,
+    first: &ScaleSpec,
+    second: &ScaleSpec,
+) -> FretBoard {
+    let first_steps = first.get_steps();
+    let second_steps = second.get_steps();
+    let mut steps: Vec<usize> = first_steps.iter().chain(second_steps).copied().collect();
+    steps.sort_unstable();
+    steps.dedup();
+    let notes: Vec<(Note, usize)> = steps
+        .into_iter()
+        .map(|step| (root.transpose(step), step))
+        .collect();
+
+    let mut fret_board = build_fret_board(tuning, starting_fret, fret_span, &notes);
+    for string in &mut fret_board.strings {
+        for position in &mut string.positions {
+            if let Some(step) = position.step {
+                position.highlight =
+                    match (first_steps.contains(&step), second_steps.contains(&step)) {
+                        (true, false) => Highlight::First,
+                        (false, true) => Highlight::Second,
+                        _ => Highlight::Normal,
+                    };
+            }
+        }
+    }
+    fret_board
+}
+
+/// The notes that are in only one of the two scales, as their degrees. A degree only in `first`
+/// is paired with a degree of the same number only in `second`, such as `6` against `b6`, and
+/// stands alone where there is none, such as the `b5` of the blues scale against the minor
+/// pentatonic scale.
+pub fn degree_differences(
+    first: &ScaleSpec,
+    second: &ScaleSpec,
+) -> Vec<(Option<Degree>, Option<Degree>)> {
+    let only_in = |scale: &ScaleSpec, other: &ScaleSpec| -> Vec<Degree> {
+        scale
+            .get_degrees()
+            .into_iter()
+            .zip(scale.get_steps())
+            .filter(|(_, step)| !other.get_steps().contains(step))
+            .map(|(degree, _)| degree)
+            .collect()
+    };
+    let only_in_first = only_in(first, second);
+    let only_in_second = only_in(second, first);
+    let mut differences = Vec::new();
+    for number in 1..=MAJOR_STEPS.len() {
+        let mut firsts = only_in_first
+            .iter()
+            .filter(|degree| degree.number == number);
+        let mut seconds = only_in_second
+            .iter()
+            .filter(|degree| degree.number == number);
+        loop {
+            match (firsts.next(), seconds.next()) {
+                (None, None) => break,
+                (first, second) => differences.push((first.copied(), second.copied())),
+            }
+        }
+    }
+    differences
+}
+
+/// Writes a difference as `6 vs b6`, with `-` for a degree missing from a scale.
+pub fn format_difference((first, second): &(Option<Degree>, Option<Degree>)) -> String {
+    let side =
+        |degree: &Option<Degree>| degree.map_or(String::from("-"), |degree| degree.to_string());
+    format!("{} vs {}", side(first), side(second))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        scales::{parse_scales, Scale},
+        tunings::Tuning,
+    };
+
+    #[test]
+    fn test_build_comparison_fret_board() {
+        let fret_board = build_comparison_fret_board(
+            &Tuning::StandardE6.get_pitches(),
+            5,
+            5,
+            Note::A,
+            &Scale::Dorian.into(),
+            &Scale::Aeolian.into(),
+        );
+        let marked = |highlight| -> Vec<(usize, usize)> {
+            fret_board
+                .scale_positions()
+                .filter(|(_, position)| position.highlight == highlight)
+                .map(|(string_index, position)| (string_index, position.fret))
+                .collect()
+        };
+        // F# and F on the A and B strings.
+        assert_eq!(marked(Highlight::First), vec![(1, 9), (4, 7)]);
+        assert_eq!(marked(Highlight::Second), vec![(1, 8), (4, 6)]);
+        assert_eq!(
+            marked(Highlight::Normal).len() + 4,
+            fret_board.scale_positions().count()
+        );
+    }
+
+    #[test]
+    fn test_degree_differences() {
+        let differences: Vec<String> =
+            degree_differences(&Scale::Dorian.into(), &Scale::Aeolian.into())
+                .iter()
+                .map(format_difference)
+                .collect();
+        assert_eq!(differences, vec!["6 vs b6"]);
+        let differences: Vec<String> = degree_differences(
+            &Scale::PentatonicMinor.into(),
+            &Scale::PentatonicBlues.into(),
+        )
+        .iter()
+        .map(format_difference)
+        .collect();
+        assert_eq!(differences, vec!["- vs b5"]);
+        let differences: Vec<String> =
+            degree_differences(&Scale::Major.into(), &Scale::PentatonicMajor.into())
+                .iter()
+                .map(format_difference)
+                .collect();
+        assert_eq!(differences, vec!["4 vs -", "7 vs -"]);
+        let differences: Vec<String> =
+            degree_differences(&Scale::WholeDiminished.into(), &Scale::Major.into())
+                .iter()
+                .map(format_difference)
+                .collect();
+        assert_eq!(differences, vec!["b3 vs 3", "b5 vs 5", "b6 vs -"]);
+        assert!(degree_differences(&Scale::Major.into(), &Scale::Ionian.into()).is_empty());
+        let custom = parse_scales("1,2,b3,5,6").unwrap().remove(0);
+        let differences: Vec<String> = degree_differences(&custom, &Scale::Dorian.into())
+            .iter()
+            .map(format_difference)
+            .collect();
+        assert_eq!(differences, vec!["- vs 4", "- vs b7"]);
+    }
+}
@@ -0,0 +1,428 @@
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use daily_scale::{
+    caged::CagedShape,
+    format::Labels,
+    fret_board::NUM_FRETS,
+    notes::{Accidental, NUM_NOTES},
+    playback::NoteValue,
+    scales::{parse_scales, ScaleSpec},
+    tunings::TuningSpec,
+};
+
+use crate::{
+    cli::{
+        validate_a4, validate_count_in, validate_starting_fret, validate_tempo, OutputFormat,
+        System, TimeZone,
+    },
+    selection::Selection,
+};
+
+/// Defaults read from the configuration file, already validated.
+#[derive(Debug, Default, PartialEq)]
+pub struct Config {
+    pub tuning: Option<TuningSpec>,
+    pub scales: Option<Vec<ScaleSpec>>,
+    pub root_notes: Option<Vec<Accidental>>,
+    pub starting_frets: Option<Vec<usize>>,
+    pub full_randomness: Option<bool>,
+    pub uncolored: Option<bool>,
+    pub labels: Option<Labels>,
+    pub output: Option<OutputFormat>,
+    pub span: Option<usize>,
+    pub full_neck: Option<bool>,
+    pub system: Option<System>,
+    pub position: Option<CagedShape>,
+    pub start_degree: Option<usize>,
+    pub arpeggio: Option<usize>,
+    pub tab: Option<bool>,
+    pub tab_root_string: Option<usize>,
+    pub repeat_top: Option<bool>,
+    pub tempo: Option<u32>,
+    pub note_value: Option<NoteValue>,
+    pub count_in: Option<u32>,
+    pub a4: Option<f64>,
+    pub log_file: Option<PathBuf>,
+    pub selection: Option<Selection>,
+    pub timezone: Option<TimeZone>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Settings {
+    tuning: Option<String>,
+    scales: Option<List<String>>,
+    root_notes: Option<List<String>>,
+    starting_frets: Option<List<usize>>,
+    full_randomness: Option<bool>,
+    uncolored: Option<bool>,
+    labels: Option<String>,
+    output: Option<String>,
+    span: Option<usize>,
+    full_neck: Option<bool>,
+    system: Option<String>,
+    position: Option<String>,
+    start_degree: Option<usize>,
+    arpeggio: Option<usize>,
+    tab: Option<bool>,
+    tab_root_string: Option<usize>,
+    repeat_top: Option<bool>,
+    tempo: Option<u32>,
+    note_value: Option<String>,
+    count_in: Option<u32>,
+    a4: Option<f64>,
+    log_file: Option<PathBuf>,
+    selection: Option<String>,
+    timezone: Option<String>,
+}
+
+/// Lists can be written either as a TOML array or as a single value.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum List<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T> List<T> {
+    fn into_vec(self) -> Vec<T> {
+        match self {
+            List::One(value) => vec![value],
+            List::Many(values) => values,
+        }
+    }
+}
+
+/// `$XDG_CONFIG_HOME/daily-scale/config.toml`, falling back to `~/.config`.
+pub fn default_config_path() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        .map(|dir| dir.join("daily-scale").join("config.toml"))
+}
+
+/// Reads the configuration file and merges the selected profile over its top-level settings.
+///
+/// A missing file is only an error if it was given explicitly or a profile was requested.
+pub fn load_config(path: Option<&Path>, profile: Option<&str>) -> Result<Config, String> {
+    let (path, explicit) = match path {
+        Some(path) => (path.to_path_buf(), true),
+        None => match default_config_path() {
+            Some(path) => (path, false),
+            None => return Ok(Config::default()),
+        },
+    };
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(_) if !explicit && profile.is_none() && !path.exists() => return Ok(Config::default()),
+        Err(error) => return Err(format!("{}: {}", path.display(), error)),
+    };
+    parse_config(&contents, profile).map_err(|error| format!("{}: {}", path.display(), error))
+}
+
+fn parse_config(contents: &str, profile: Option<&str>) -> Result<Config, String> {
+    let mut table = contents
+        .parse::<toml::Table>()
+        .map_err(|error| error.to_string())?;
+    let profiles = table.remove("profile");
+    let settings = toml::Value::Table(table)
+        .try_into::<Settings>()
+        .map_err(|error| error.to_string())?;
+    let mut config = settings.into_config("")?;
+
+    if let Some(profile) = profile {
+        let profile_settings = profiles
+            .as_ref()
+            .and_then(|profiles| profiles.get(profile))
+            .ok_or_else(|| format!("profile '{}' is not defined", profile))?;
+        let profile_settings = profile_settings
+            .clone()
+            .try_into::<Settings>()
+            .map_err(|error| format!("in profile '{}': {}", profile, error))?;
+        let profile_config = profile_settings.into_config(&format!("profile.{}.", profile))?;
+        config = profile_config.or(config);
+    }
+    Ok(config)
+}
+
+impl Settings {
+    fn into_config(self, key_prefix: &str) -> Result<Config, String> {
+        let invalid = |key: &str, error: String| {
+            format!("invalid value for key '{}{}': {}", key_prefix, key, error)
+        };
+        let tuning = self
+            .tuning
+            .map(|tuning| tuning.parse::<TuningSpec>())
+            .transpose()
+            .map_err(|error| invalid("tuning", error))?;
+        let scales = self
+            .scales
+            .map(|scales| parse_scales(&scales.into_vec().join(",")))
+            .transpose()
+            .map_err(|error| invalid("scales", error))?;
+        let root_notes = self
+            .root_notes
+            .map(|notes| {
+                notes
+                    .into_vec()
+                    .iter()
+                    .map(|note| Accidental::from_str(note, true))
+                    .collect::<Result<Vec<Accidental>, String>>()
+            })
+            .transpose()
+            .map_err(|error| invalid("root_notes", error))?;
+        let starting_frets = self
+            .starting_frets
+            .map(|frets| {
+                frets
+                    .into_vec()
+                    .into_iter()
+                    .map(|fret| validate_starting_fret(fret, 1))
+                    .collect::<Result<Vec<usize>, String>>()
+            })
+            .transpose()
+            .map_err(|error| invalid("starting_frets", error))?;
+        let labels = self
+            .labels
+            .map(|labels| Labels::from_str(&labels, true))
+            .transpose()
+            .map_err(|error| invalid("labels", error))?;
+        let output = self
+            .output
+            .map(|output| OutputFormat::from_str(&output, true))
+            .transpose()
+            .map_err(|error| invalid("output", error))?;
+        if self
+            .span
+            .is_some_and(|span| !(1..=NUM_FRETS + 1).contains(&span))
+        {
+            return Err(invalid(
+                "span",
+                format!("Number must be between 1 and {}", NUM_FRETS + 1),
+            ));
+        }
+        let system = self
+            .system
+            .map(|system| System::from_str(&system, true))
+            .transpose()
+            .map_err(|error| invalid("system", error))?;
+        let position = self
+            .position
+            .map(|position| CagedShape::from_str(&position, true))
+            .transpose()
+            .map_err(|error| invalid("position", error))?;
+        if self
+            .start_degree
+            .is_some_and(|degree| !(1..=NUM_NOTES).contains(&degree))
+        {
+            return Err(invalid(
+                "start_degree",
+                format!("Number must be between 1 and {}", NUM_NOTES),
+            ));
+        }
+        if self
+            .arpeggio
+            .is_some_and(|degree| !(1..=NUM_NOTES).contains(&degree))
+        {
+            return Err(invalid(
+                "arpeggio",
+                format!("Number must be between 1 and {}", NUM_NOTES),
+            ));
+        }
+        if self.tab_root_string == Some(0) {
+            return Err(invalid(
+                "tab_root_string",
+                String::from("Number must be at least 1"),
+            ));
+        }
+        let tempo = self
+            .tempo
+            .map(validate_tempo)
+            .transpose()
+            .map_err(|error| invalid("tempo", error))?;
+        let note_value = self
+            .note_value
+            .map(|note_value| NoteValue::from_str(&note_value, true))
+            .transpose()
+            .map_err(|error| invalid("note_value", error))?;
+        let count_in = self
+            .count_in
+            .map(validate_count_in)
+            .transpose()
+            .map_err(|error| invalid("count_in", error))?;
+        let a4 = self
+            .a4
+            .map(validate_a4)
+            .transpose()
+            .map_err(|error| invalid("a4", error))?;
+        let selection = self
+            .selection
+            .map(|selection| Selection::from_str(&selection, true))
+            .transpose()
+            .map_err(|error| invalid("selection", error))?;
+        let timezone = self
+            .timezone
+            .map(|timezone| timezone.parse::<TimeZone>())
+            .transpose()
+            .map_err(|error| invalid("timezone", error))?;
+        Ok(Config {
+            tuning,
+            scales,
+            root_notes,
+            starting_frets,
+            full_randomness: self.full_randomness,
+            uncolored: self.uncolored,
+            labels,
+            output,
+            span: self.span,
+            full_neck: self.full_neck,
+            system,
+            position,
+            start_degree: self.start_degree,
+            arpeggio: self.arpeggio,
+            tab: self.tab,
+            tab_root_string: self.tab_root_string,
+            repeat_top: self.repeat_top,
+            tempo,
+            note_value,
+            count_in,
+            a4,
+            log_file: self.log_file,
+            selection,
+            timezone,
+        })
+    }
+}
+
+impl Config {
+    /// Keeps the values set in `self` and takes the rest from `other`.
+    fn or(self, other: Config) -> Config {
+        Config {
+            tuning: self.tuning.or(other.tuning),
+            scales: self.scales.or(other.scales),
+            root_notes: self.root_notes.or(other.root_notes),
+            starting_frets: self.starting_frets.or(other.starting_frets),
+            full_randomness: self.full_randomness.or(other.full_randomness),
+            uncolored: self.uncolored.or(other.uncolored),
+            labels: self.labels.or(other.labels),
+            output: self.output.or(other.output),
+            span: self.span.or(other.span),
+            full_neck: self.full_neck.or(other.full_neck),
+            system: self.system.or(other.system),
+            position: self.position.or(other.position),
+            start_degree: self.start_degree.or(other.start_degree),
+            arpeggio: self.arpeggio.or(other.arpeggio),
+            tab: self.tab.or(other.tab),
+            tab_root_string: self.tab_root_string.or(other.tab_root_string),
+            repeat_top: self.repeat_top.or(other.repeat_top),
+            tempo: self.tempo.or(other.tempo),
+            note_value: self.note_value.or(other.note_value),
+            count_in: self.count_in.or(other.count_in),
+            a4: self.a4.or(other.a4),
+            log_file: self.log_file.or(other.log_file),
+            selection: self.selection.or(other.selection),
+            timezone: self.timezone.or(other.timezone),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+    use daily_scale::{scales::Scale, tunings::Tuning};
+
+    const CONFIG: &str = r#"
+tuning = "drop-d6"
+scales = "dorian,aeolian"
+uncolored = true
+
+[profile.jazz]
+scales = ["melodic-minor", "1,2,b3,4,5,6,b7"]
+root_notes = ["b-flat", "f"]
+starting_frets = [3, 8]
+"#;
+
+    #[test]
+    fn test_parse_config() {
+        let config = parse_config(CONFIG, None).unwrap();
+        assert_eq!(config.tuning, Some(TuningSpec::Preset(Tuning::DropD6)));
+        assert_eq!(
+            config.scales,
+            Some(vec![Scale::Dorian.into(), Scale::Aeolian.into()])
+        );
+        assert_eq!(config.uncolored, Some(true));
+        assert_eq!(config.root_notes, None);
+
+        let config = parse_config("timezone = \"-05:30\"", None).unwrap();
+        assert_eq!(
+            config.timezone,
+            Some(TimeZone::Offset(
+                FixedOffset::west_opt(5 * 3600 + 1800).unwrap()
+            ))
+        );
+        let config = parse_config("timezone = \"Local\"", None).unwrap();
+        assert_eq!(config.timezone, Some(TimeZone::Local));
+        let config = parse_config("labels = \"intervals\"", None).unwrap();
+        assert_eq!(config.labels, Some(Labels::Intervals));
+        let config = parse_config("tab_root_string = 13", None).unwrap();
+        assert_eq!(config.tab_root_string, Some(13));
+    }
+
+    #[test]
+    fn test_parse_config_profile() {
+        let config = parse_config(CONFIG, Some("jazz")).unwrap();
+        assert_eq!(config.tuning, Some(TuningSpec::Preset(Tuning::DropD6)));
+        assert_eq!(config.scales.unwrap().len(), 2);
+        assert_eq!(
+            config.root_notes,
+            Some(vec![Accidental::BFlat, Accidental::F])
+        );
+        assert_eq!(config.starting_frets, Some(vec![3, 8]));
+        assert!(parse_config(CONFIG, Some("metal"))
+            .unwrap_err()
+            .contains("profile 'metal'"));
+    }
+
+    #[test]
+    fn test_parse_config_errors() {
+        assert!(parse_config("tuning = \"drop-q6\"", None)
+            .unwrap_err()
+            .contains("key 'tuning'"));
+        assert!(parse_config(
+            CONFIG.replace("melodic-minor", "melodic").as_str(),
+            Some("jazz")
+        )
+        .unwrap_err()
+        .contains("key 'profile.jazz.scales'"));
+        assert!(parse_config("tunning = \"drop-d6\"", None).is_err());
+        assert!(parse_config("starting_frets = 30", None).is_err());
+        assert!(parse_config("span = 0", None)
+            .unwrap_err()
+            .contains("key 'span'"));
+        assert!(parse_config("tempo = 1000", None)
+            .unwrap_err()
+            .contains("key 'tempo'"));
+        assert!(parse_config("note_value = \"dotted\"", None)
+            .unwrap_err()
+            .contains("key 'note_value'"));
+        assert!(parse_config("labels = \"frets\"", None)
+            .unwrap_err()
+            .contains("key 'labels'"));
+        assert!(parse_config("tab_root_string = 0", None)
+            .unwrap_err()
+            .contains("key 'tab_root_string'"));
+        assert!(parse_config("position = \"B\"", None)
+            .unwrap_err()
+            .contains("key 'position'"));
+        assert!(parse_config("timezone = \"Europe/Paris\"", None)
+            .unwrap_err()
+            .contains("key 'timezone'"));
+    }
+}
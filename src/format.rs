@@ -0,0 +1,72 @@
+use clap::ValueEnum;
+use colored::{Color, Colorize};
+
+use crate::{scales::Degree, spelling::Spelling};
+
+/// How notes are written out when rendering.
+#[derive(Default)]
+pub struct Format {
+    /// How the notes are named.
+    pub spelling: Spelling,
+    /// Color notes by their step from the root using ANSI escape codes.
+    pub colored: bool,
+    /// What the notes on the fret board are labeled with.
+    pub labels: Labels,
+    /// The degrees of the scale for [`Labels::Degrees`]; other steps are named by
+    /// [`Degree::from_step`].
+    pub degrees: Vec<Degree>,
+}
+
+impl Format {
+    /// The degree of the scale `step` half steps above the root.
+    pub fn degree(&self, step: usize) -> Degree {
+        self.degrees
+            .iter()
+            .find(|degree| degree.get_step() == Some(step))
+            .copied()
+            .unwrap_or_else(|| Degree::from_step(step))
+    }
+}
+
+/// What the notes on the fret board are labeled with.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Labels {
+    #[default]
+    Notes,
+    Intervals,
+    Degrees,
+    Fingers,
+}
+
+/// The color of a note by its semitone `step` from the root, if it has one.
+pub fn step_color(step: usize) -> Option<Color> {
+    match step {
+        0 => Some(Color::Green),
+        3 => Some(Color::Red),
+        4 => Some(Color::Red),
+        5 => Some(Color::Cyan),
+        6 => Some(Color::Black),
+        7 => Some(Color::Blue),
+        9 => Some(Color::Magenta),
+        10 => Some(Color::Yellow),
+        11 => Some(Color::Yellow),
+        _ => None,
+    }
+}
+
+/// Colors `note_string` according to the semitone `step` of the note from the root.
+pub fn format_with_color(note_string: &str, step: usize, colored: bool) -> String {
+    match step_color(step) {
+        Some(color) if colored => format!("{}", note_string.color(color)),
+        _ => String::from(note_string),
+    }
+}
+
+/// Writes `note_string` in a faint color for a scale note that is shown dimmed.
+pub fn format_dimmed(note_string: &str, colored: bool) -> String {
+    if colored {
+        format!("{}", note_string.dimmed())
+    } else {
+        String::from(note_string)
+    }
+}
@@ -1,55 +1,216 @@
-use strum::IntoEnumIterator;
-
 use crate::{
-    cli::{format_with_color, Format},
-    notes::{Note, NUM_NOTES},
-    tunings::Tuning,
+    format::{format_dimmed, format_with_color, Format, Labels},
+    notes::Note,
+    pitch::Pitch,
+    scales::Degree,
 };
 
+/// Highest fret on the neck.
 pub const NUM_FRETS: usize = 24;
 
+/// Number of frets shown in the practice window by default.
 pub const FRET_SPAN: usize = 5;
 
+/// A single fret of a string inside the practice window.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FretPosition {
+    pub fret: usize,
+    pub note: Note,
+    /// The exact pitch sounding at this fret, with its octave.
+    pub pitch: Pitch,
+    /// Semitone step from the root, if the note belongs to the scale.
+    pub step: Option<usize>,
+    /// How the scale note stands out from the others.
+    pub highlight: Highlight,
+}
+
+/// How a scale note is marked when the fret board is drawn.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Highlight {
+    #[default]
+    Normal,
+    /// Shown faintly, such as a scale note outside the chord of an arpeggio, or between dots
+    /// such as `.b7.` without colors.
+    Dimmed,
+    /// Only in the first of two compared scales, written in parentheses.
+    First,
+    /// Only in the second of two compared scales, written in brackets.
+    Second,
+    /// Left for the player to work out, written as `?`.
+    Hidden,
+}
+
+/// The frets of one string inside the practice window.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FretBoardString {
+    pub open_pitch: Pitch,
+    pub positions: Vec<FretPosition>,
+}
+
+/// The practice window of the fret board.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FretBoard {
+    pub starting_fret: usize,
+    /// Number of frets in the window, including `starting_fret`.
+    pub fret_span: usize,
+    /// Strings ordered from the lowest to the highest, like [`crate::tunings::Tuning::get_pitches`].
+    pub strings: Vec<FretBoardString>,
+}
+
+impl FretBoard {
+    /// Every position holding a scale note, with the index of its string.
+    pub fn scale_positions(&self) -> impl Iterator<Item = (usize, &FretPosition)> {
+        self.strings
+            .iter()
+            .enumerate()
+            .flat_map(|(string_index, string)| {
+                string
+                    .positions
+                    .iter()
+                    .filter(|position| position.step.is_some())
+                    .map(move |position| (string_index, position))
+            })
+    }
+}
+
+/// Lays out the scale over `fret_span` frets starting at `starting_fret`, for a tuning
+/// given by its open string pitches from the lowest string to the highest.
+///
+/// The window must fit on the neck: `starting_fret + fret_span` is at most `NUM_FRETS + 1`.
 pub fn build_fret_board(
-    tuning: Tuning,
+    tuning: &[Pitch],
     starting_fret: usize,
+    fret_span: usize,
     notes_in_scale: &[(Note, usize)],
-    format: &Format,
-) -> Vec<String> {
-    let mut fret_board = Vec::new();
-    let notes_in_tuning = tuning.get_notes();
-    for (string_counter, string) in notes_in_tuning.iter().enumerate() {
-        let string_char = if string_counter < (notes_in_tuning.len() - NUM_THIN_STRINGS) {
+) -> FretBoard {
+    let strings = tuning
+        .iter()
+        .map(|string| build_fret_board_string(starting_fret, fret_span, notes_in_scale, *string))
+        .collect();
+    FretBoard {
+        starting_fret,
+        fret_span,
+        strings,
+    }
+}
+
+/// Renders the fret board as text, highest string first, followed by the fret numbers.
+pub fn render_fret_board(fret_board: &FretBoard, format: &Format) -> Vec<String> {
+    let mut lines = Vec::new();
+    let num_strings = fret_board.strings.len();
+    let lowest_fret = lowest_fretted(fret_board);
+    let open_width = fret_board
+        .scale_positions()
+        .filter(|(_, position)| position.fret == 0)
+        .map(|(_, position)| {
+            note_label(position, lowest_fret, format)
+                .len()
+                .max(NOTE_WIDTH)
+        })
+        .max()
+        .unwrap_or(NOTE_WIDTH);
+    for (string_counter, string) in fret_board.strings.iter().enumerate() {
+        let string_char = if is_wound_string(string_counter, num_strings) {
             '='
         } else {
             '-'
         };
-        let fret_board_string =
-            build_fret_board_string(starting_fret, notes_in_scale, *string, string_char, format);
-        fret_board.insert(0, fret_board_string);
+        lines.insert(
+            0,
+            render_fret_board_string(string, string_char, open_width, lowest_fret, format),
+        );
     }
-    let fret_num_string = build_fret_num_string(starting_fret);
-    fret_board.push(fret_num_string);
+    lines.push(build_fret_num_string(
+        fret_board.starting_fret,
+        fret_board.fret_span,
+        open_width,
+    ));
+    lines
+}
+
+/// The lowest fret holding a scale note other than the open strings, where the index finger of
+/// [`Labels::Fingers`] goes.
+pub(crate) fn lowest_fretted(fret_board: &FretBoard) -> usize {
     fret_board
+        .scale_positions()
+        .map(|(_, position)| position.fret)
+        .filter(|fret| *fret > 0)
+        .min()
+        .unwrap_or(fret_board.starting_fret)
 }
 
 const NUM_THIN_STRINGS: usize = 3;
 
-const FRET_LENGTH: [usize; 25] = [
+/// Whether the string at `string_index`, counted from the lowest string, is drawn thick.
+pub(crate) fn is_wound_string(string_index: usize, num_strings: usize) -> bool {
+    string_index < num_strings.saturating_sub(NUM_THIN_STRINGS)
+}
+
+/// Width of a note inside a fret; longer names such as `F##` take space from the fret.
+const NOTE_WIDTH: usize = 2;
+
+pub(crate) const FRET_LENGTH: [usize; NUM_FRETS + 1] = [
     0, 10, 10, 9, 9, 9, 8, 8, 8, 8, 7, 7, 7, 7, 7, 6, 6, 6, 6, 6, 6, 5, 5, 5, 5,
 ];
 
-fn format_note(note: Note, step: usize, string_char: char, format: &Format) -> String {
-    let Format { flat, colored } = *format;
-    let note_string = note.to_str(flat);
-    let colored_note = format_with_color(note_string, step, colored);
-    if note_string.len() == 1 {
+fn format_note(
+    label: &str,
+    step: usize,
+    highlight: Highlight,
+    string_char: char,
+    colored: bool,
+) -> String {
+    let colored_note = match highlight {
+        Highlight::Dimmed => format_dimmed(label, colored),
+        Highlight::Hidden => String::from(label),
+        _ => format_with_color(label, step, colored),
+    };
+    if label.len() == 1 {
         format!("{}{}", colored_note, string_char)
     } else {
         colored_note
     }
 }
 
+/// What the scale note at `position` is labeled with, before the marks of its highlight and
+/// any colors. Fingers count one per fret from `lowest_fret`, the lowest fretted scale note of
+/// the fret board, with the little finger stretching to any fret above.
+pub(crate) fn position_label(
+    position: &FretPosition,
+    lowest_fret: usize,
+    format: &Format,
+) -> String {
+    let step = position.step.unwrap_or_default();
+    match format.labels {
+        Labels::Notes => format.spelling.name(position.note),
+        Labels::Intervals if step == 0 => String::from("R"),
+        Labels::Intervals => Degree::from_step(step).to_string(),
+        Labels::Degrees => format.degree(step).to_string(),
+        Labels::Fingers if position.fret == 0 => String::from("0"),
+        Labels::Fingers => (position.fret + 1)
+            .saturating_sub(lowest_fret)
+            .clamp(1, NUM_FINGERS)
+            .to_string(),
+    }
+}
+
+/// The label of the scale note at `position` with the marks of its highlight, before any
+/// colors.
+fn note_label(position: &FretPosition, lowest_fret: usize, format: &Format) -> String {
+    let label = position_label(position, lowest_fret, format);
+    match position.highlight {
+        Highlight::Normal => label,
+        Highlight::Dimmed if format.colored => label,
+        Highlight::Dimmed => format!(".{}.", label),
+        Highlight::First => format!("({})", label),
+        Highlight::Second => format!("[{}]", label),
+        Highlight::Hidden => String::from("?"),
+    }
+}
+
+/// Number of fingers of the fretting hand.
+const NUM_FINGERS: usize = 4;
+
 fn format_fret_num(fret_num: usize) -> String {
     let fret_num_string = fret_num.to_string();
     if fret_num_string.len() == 1 {
@@ -61,46 +222,79 @@ fn format_fret_num(fret_num: usize) -> String {
 
 fn build_fret_board_string(
     starting_fret: usize,
+    fret_span: usize,
     notes_in_scale: &[(Note, usize)],
-    string: Note,
+    string: Pitch,
+) -> FretBoardString {
+    let positions = (starting_fret..(starting_fret + fret_span))
+        .map(|fret| {
+            let pitch = string.transpose(fret);
+            let step = notes_in_scale
+                .iter()
+                .find(|(note_in_scale, _)| *note_in_scale == pitch.note)
+                .map(|(_, step)| *step);
+            FretPosition {
+                fret,
+                note: pitch.note,
+                pitch,
+                step,
+                highlight: Highlight::Normal,
+            }
+        })
+        .collect();
+    FretBoardString {
+        open_pitch: string,
+        positions,
+    }
+}
+
+fn render_fret_board_string(
+    string: &FretBoardString,
     string_char: char,
+    open_width: usize,
+    lowest_fret: usize,
     format: &Format,
 ) -> String {
     let mut fret_board_string = String::new();
-    let notes = Note::iter().collect::<Vec<Note>>();
-    let empty_string_note_index = notes.iter().position(|&note| note == string).unwrap();
-    let notes_in_string = (0..=NUM_NOTES)
-        .map(|fret| {
-            let note_index = (empty_string_note_index + fret) % NUM_NOTES;
-            notes[note_index]
-        })
-        .collect::<Vec<Note>>();
-    for fret in starting_fret..(starting_fret + FRET_SPAN) {
-        let note = notes_in_string[fret % NUM_NOTES];
+    for position in &string.positions {
+        let &FretPosition {
+            fret,
+            step,
+            highlight,
+            ..
+        } = position;
+        let label = note_label(position, lowest_fret, format);
+        let note_width = label.len().max(NOTE_WIDTH);
         if fret == 0 {
-            if let Some((_, step)) = notes_in_scale
-                .iter()
-                .find(|(note_in_scale, _)| *note_in_scale == note)
-            {
-                fret_board_string.push_str(format_note(note, *step, string_char, format).as_str());
+            if let Some(step) = step {
+                fret_board_string.push_str(
+                    format_note(&label, step, highlight, string_char, format.colored).as_str(),
+                );
+                for _ in note_width..open_width {
+                    fret_board_string.push(string_char);
+                }
             } else {
-                fret_board_string.push(string_char);
-                fret_board_string.push(string_char);
+                for _ in 0..open_width {
+                    fret_board_string.push(string_char);
+                }
             }
         } else {
             fret_board_string.push('|');
             let fret_length = FRET_LENGTH[fret];
-            if let Some((_, step)) = notes_in_scale
-                .iter()
-                .find(|(note_in_scale, _)| *note_in_scale == note)
-            {
+            if let Some(step) = step {
                 let fret_length_odd = !fret_length.is_multiple_of(2);
                 let first_half_fret_length = fret_length / 2 - if fret_length_odd { 0 } else { 1 };
-                let second_half_fret_length = fret_length / 2 - 1;
+                // Long labels such as `(F#)` also take space before the note on short frets.
+                let first_half_fret_length =
+                    first_half_fret_length.min(fret_length.saturating_sub(note_width));
+                let second_half_fret_length =
+                    fret_length.saturating_sub(first_half_fret_length + note_width);
                 for _ in 0..first_half_fret_length {
                     fret_board_string.push(string_char);
                 }
-                fret_board_string.push_str(format_note(note, *step, string_char, format).as_str());
+                fret_board_string.push_str(
+                    format_note(&label, step, highlight, string_char, format.colored).as_str(),
+                );
                 for _ in 0..second_half_fret_length {
                     fret_board_string.push(string_char);
                 }
@@ -115,12 +309,13 @@ fn build_fret_board_string(
     fret_board_string
 }
 
-fn build_fret_num_string(starting_fret: usize) -> String {
+fn build_fret_num_string(starting_fret: usize, fret_span: usize, open_width: usize) -> String {
     let mut fret_num_string = String::new();
-    (starting_fret..(starting_fret + FRET_SPAN)).for_each(|fret| {
+    (starting_fret..(starting_fret + fret_span)).for_each(|fret| {
         if fret == 0 {
-            fret_num_string.push(' ');
-            fret_num_string.push(' ');
+            for _ in 0..open_width {
+                fret_num_string.push(' ');
+            }
         } else {
             fret_num_string.push('|');
             let fret_length = FRET_LENGTH[fret];
@@ -143,44 +338,70 @@ fn build_fret_num_string(starting_fret: usize) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        notes::Accidental,
+        scales::Scale,
+        spelling::{spell_scale, Spelling},
+        tunings::Tuning,
+    };
 
     #[test]
     fn test_format_note() {
+        assert_eq!(format_note("A", 0, Highlight::Normal, '-', false), "A-");
+        assert_eq!(format_note("C#", 0, Highlight::Normal, '=', false), "C#");
+        assert_eq!(format_note("[F]", 8, Highlight::Second, '-', false), "[F]");
+    }
+
+    #[test]
+    fn test_note_label() {
+        let position = |fret, note, step, highlight| FretPosition {
+            fret,
+            note,
+            pitch: Pitch::new(note, 3),
+            step: Some(step),
+            highlight,
+        };
+        let flats = Format {
+            spelling: Spelling::Flats,
+            ..Default::default()
+        };
+        let label = |position: &FretPosition, format: &Format| note_label(position, 5, format);
         assert_eq!(
-            format_note(
-                Note::A,
-                0,
-                '-',
-                &Format {
-                    flat: true,
-                    colored: false
-                }
-            ),
-            "A-"
+            label(&position(6, Note::GSharp, 0, Highlight::Normal), &flats),
+            "Ab"
         );
         assert_eq!(
-            format_note(
-                Note::CSharp,
-                0,
-                '=',
-                &Format {
-                    flat: false,
-                    colored: false
-                },
-            ),
-            "C#"
+            label(&position(6, Note::DSharp, 3, Highlight::Dimmed), &flats),
+            ".Eb."
         );
         assert_eq!(
-            format_note(
-                Note::GSharp,
-                0,
-                '-',
-                &Format {
-                    flat: true,
-                    colored: false
-                },
-            ),
-            "Ab"
+            label(&position(6, Note::F, 8, Highlight::Second), &flats),
+            "[F]"
+        );
+
+        let with_labels = |labels| Format {
+            labels,
+            degrees: Scale::Lydian.get_degrees(),
+            ..Default::default()
+        };
+        let intervals = with_labels(Labels::Intervals);
+        let degrees = with_labels(Labels::Degrees);
+        let fingers = with_labels(Labels::Fingers);
+        let root = position(5, Note::F, 0, Highlight::Normal);
+        let fourth = position(7, Note::B, 6, Highlight::Normal);
+        assert_eq!(label(&root, &intervals), "R");
+        assert_eq!(label(&fourth, &intervals), "b5");
+        assert_eq!(label(&root, &degrees), "1");
+        assert_eq!(label(&fourth, &degrees), "#4");
+        assert_eq!(label(&root, &fingers), "1");
+        assert_eq!(label(&fourth, &fingers), "3");
+        assert_eq!(
+            label(&position(9, Note::CSharp, 8, Highlight::First), &fingers),
+            "(4)"
+        );
+        assert_eq!(
+            label(&position(0, Note::E, 11, Highlight::Normal), &fingers),
+            "0"
         );
     }
 
@@ -193,54 +414,107 @@ mod tests {
     #[test]
     fn test_build_fret_board_string() {
         assert_eq!(
-            build_fret_board_string(
-                5,
-                &[(Note::A, 0), (Note::B, 2), (Note::C, 3)],
-                Note::E,
+            render_fret_board_string(
+                &build_fret_board_string(
+                    5,
+                    FRET_SPAN,
+                    &[(Note::A, 0), (Note::B, 2), (Note::C, 3)],
+                    Pitch::new(Note::E, 2)
+                ),
                 '=',
+                2,
+                5,
                 &Format {
-                    flat: false,
-                    colored: false
+                    spelling: Spelling::Sharps,
+                    colored: false,
+                    ..Default::default()
                 },
             ),
             "|====A====|========|===B====|===C====|========|"
         );
         assert_eq!(
-            build_fret_board_string(
-                12,
-                &[(Note::DSharp, 1), (Note::E, 2), (Note::FSharp, 4)],
-                Note::D,
+            render_fret_board_string(
+                &build_fret_board_string(
+                    12,
+                    FRET_SPAN,
+                    &[(Note::DSharp, 1), (Note::E, 2), (Note::FSharp, 4)],
+                    Pitch::new(Note::D, 3)
+                ),
                 '=',
+                2,
+                13,
                 &Format {
-                    flat: false,
-                    colored: false
+                    spelling: Spelling::Sharps,
+                    colored: false,
+                    ..Default::default()
                 },
             ),
             "|=======|===D#==|===E===|======|==F#==|"
         );
         assert_eq!(
-            build_fret_board_string(
-                0,
-                &[(Note::B, 0), (Note::CSharp, 2), (Note::DSharp, 4)],
-                Note::B,
+            render_fret_board_string(
+                &build_fret_board_string(
+                    0,
+                    FRET_SPAN,
+                    &[(Note::B, 0), (Note::CSharp, 2), (Note::DSharp, 4)],
+                    Pitch::new(Note::B, 3)
+                ),
                 '-',
+                2,
+                1,
                 &Format {
-                    flat: true,
-                    colored: false
+                    spelling: Spelling::Flats,
+                    colored: false,
+                    ..Default::default()
                 },
             ),
             "B-|----------|----Db----|---------|----Eb---|"
         );
     }
 
+    #[test]
+    fn test_scale_positions() {
+        let fret_board = build_fret_board(
+            &Tuning::DropD6.get_pitches(),
+            0,
+            FRET_SPAN,
+            &[(Note::D, 0), (Note::E, 2), (Note::FSharp, 4)],
+        );
+        assert_eq!(
+            fret_board
+                .scale_positions()
+                .map(|(string_index, position)| (string_index, position.fret, position.note))
+                .collect::<Vec<(usize, usize, Note)>>(),
+            vec![
+                (0, 0, Note::D),
+                (0, 2, Note::E),
+                (0, 4, Note::FSharp),
+                (2, 0, Note::D),
+                (2, 2, Note::E),
+                (2, 4, Note::FSharp),
+                (4, 3, Note::D),
+                (5, 0, Note::E),
+                (5, 2, Note::FSharp),
+            ]
+        );
+        assert_eq!(
+            fret_board.strings[0].positions[4].pitch,
+            Pitch::new(Note::FSharp, 2)
+        );
+        assert_eq!(
+            fret_board.strings[5].positions[2].pitch,
+            Pitch::new(Note::FSharp, 4)
+        );
+    }
+
     #[test]
     fn test_build_fret_num_string() {
         assert_eq!(
-            build_fret_num_string(0),
+            build_fret_num_string(0, FRET_SPAN, 2),
             "  |    1     |    2     |    3    |    4    |"
         );
         assert_eq!(
-            build_fret_num_string(12),
+            build_fret_num_string(12, FRET_SPAN, 2),
             "|   12  |   13  |   14  |  15  |  16  |"
         );
     }
@@ -248,21 +522,25 @@ mod tests {
     #[test]
     fn test_build_fret_board() {
         assert_eq!(
-            build_fret_board(
-                Tuning::OpenG6,
-                0,
-                &[
-                    (Note::A, 0),
-                    (Note::B, 2),
-                    (Note::C, 3),
-                    (Note::D, 5),
-                    (Note::E, 7),
-                    (Note::F, 8),
-                    (Note::GSharp, 11),
-                ],
+            render_fret_board(
+                &build_fret_board(
+                    &Tuning::OpenG6.get_pitches(),
+                    0,
+                    FRET_SPAN,
+                    &[
+                        (Note::A, 0),
+                        (Note::B, 2),
+                        (Note::C, 3),
+                        (Note::D, 5),
+                        (Note::E, 7),
+                        (Note::F, 8),
+                        (Note::GSharp, 11),
+                    ]
+                ),
                 &Format {
-                    flat: false,
-                    colored: false
+                    spelling: Spelling::Sharps,
+                    colored: false,
+                    ..Default::default()
                 },
             ),
             vec![
@@ -276,21 +554,25 @@ mod tests {
             ]
         );
         assert_eq!(
-            build_fret_board(
-                Tuning::StandardB7,
-                7,
-                &[
-                    (Note::A, 0),
-                    (Note::B, 2),
-                    (Note::CSharp, 4),
-                    (Note::D, 5),
-                    (Note::E, 7),
-                    (Note::FSharp, 9),
-                    (Note::G, 10),
-                ],
+            render_fret_board(
+                &build_fret_board(
+                    &Tuning::StandardB7.get_pitches(),
+                    7,
+                    FRET_SPAN,
+                    &[
+                        (Note::A, 0),
+                        (Note::B, 2),
+                        (Note::CSharp, 4),
+                        (Note::D, 5),
+                        (Note::E, 7),
+                        (Note::FSharp, 9),
+                        (Note::G, 10),
+                    ]
+                ),
                 &Format {
-                    flat: true,
-                    colored: false
+                    spelling: Spelling::Flats,
+                    colored: false,
+                    ..Default::default()
                 },
             ),
             vec![
@@ -305,20 +587,24 @@ mod tests {
             ]
         );
         assert_eq!(
-            build_fret_board(
-                Tuning::OpenE6,
-                15,
-                &[
-                    (Note::A, 0),
-                    (Note::ASharp, 1),
-                    (Note::CSharp, 4),
-                    (Note::DSharp, 6),
-                    (Note::FSharp, 9),
-                    (Note::GSharp, 11),
-                ],
+            render_fret_board(
+                &build_fret_board(
+                    &Tuning::OpenE6.get_pitches(),
+                    15,
+                    FRET_SPAN,
+                    &[
+                        (Note::A, 0),
+                        (Note::ASharp, 1),
+                        (Note::CSharp, 4),
+                        (Note::DSharp, 6),
+                        (Note::FSharp, 9),
+                        (Note::GSharp, 11),
+                    ]
+                ),
                 &Format {
-                    flat: false,
-                    colored: false
+                    spelling: Spelling::Sharps,
+                    colored: false,
+                    ..Default::default()
                 },
             ),
             vec![
@@ -332,4 +618,118 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_render_fret_board_labels() {
+        let fret_board = build_fret_board(
+            &Tuning::StandardE6.get_pitches(),
+            5,
+            FRET_SPAN,
+            &Scale::PentatonicMinor.get_notes(Note::A),
+        );
+        let render = |labels| {
+            render_fret_board(
+                &fret_board,
+                &Format {
+                    labels,
+                    ..Default::default()
+                },
+            )
+        };
+        let notes = render(Labels::Notes);
+        assert_eq!(
+            render(Labels::Intervals),
+            vec![
+                "|----R----|--------|--------|---b3---|--------|",
+                "|----5----|--------|--------|---b7---|--------|",
+                "|----b3---|--------|---4----|--------|---5----|",
+                "|====b7===|========|===R====|========|========|",
+                "|====4====|========|===5====|========|========|",
+                "|====R====|========|========|===b3===|========|",
+                "|    5    |   6    |   7    |   8    |   9    |",
+            ]
+        );
+        assert_eq!(
+            render(Labels::Fingers)[0],
+            "|----1----|--------|--------|---4----|--------|"
+        );
+        for lines in [render(Labels::Degrees), render(Labels::Fingers)] {
+            let widths: Vec<usize> = lines.iter().map(String::len).collect();
+            assert_eq!(
+                widths,
+                notes.iter().map(String::len).collect::<Vec<usize>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_render_fret_board_dimmed() {
+        let mut fret_board = build_fret_board(
+            &Tuning::StandardE6.get_pitches(),
+            5,
+            FRET_SPAN,
+            &Scale::PentatonicMinor.get_notes(Note::A),
+        );
+        for position in &mut fret_board.strings[5].positions {
+            if position.step.is_some_and(|step| step != 0) {
+                position.highlight = Highlight::Dimmed;
+            }
+        }
+        let render = |labels| {
+            render_fret_board(
+                &fret_board,
+                &Format {
+                    labels,
+                    degrees: Scale::PentatonicMinor.get_degrees(),
+                    ..Default::default()
+                },
+            )[0]
+            .clone()
+        };
+        assert_eq!(
+            render(Labels::Notes),
+            "|----A----|--------|--------|---.C.--|--------|"
+        );
+        assert_eq!(
+            render(Labels::Intervals),
+            "|----R----|--------|--------|---.b3.-|--------|"
+        );
+        assert_eq!(
+            render(Labels::Degrees),
+            "|----1----|--------|--------|---.b3.-|--------|"
+        );
+        assert_eq!(
+            render(Labels::Fingers),
+            "|----1----|--------|--------|---.4.--|--------|"
+        );
+    }
+
+    #[test]
+    fn test_render_fret_board_spelling() {
+        let degrees = Scale::HarmonicMinor.get_degrees();
+        assert_eq!(
+            render_fret_board(
+                &build_fret_board(
+                    &Tuning::StandardE6.get_pitches(),
+                    0,
+                    FRET_SPAN,
+                    &Scale::HarmonicMinor.get_notes(Note::GSharp),
+                ),
+                &Format {
+                    spelling: Spelling::Scale(spell_scale(Accidental::GSharp.into(), &degrees)),
+                    colored: false,
+                    ..Default::default()
+                },
+            ),
+            vec![
+                "E--|----------|----------|----F##--|----G#---|",
+                "B--|----------|----C#----|---------|----D#---|",
+                "F##|----G#----|----------|----A#---|----B----|",
+                "===|====D#====|====E=====|=========|=========|",
+                "===|====A#====|====B=====|=========|====C#===|",
+                "E==|==========|==========|====F##==|====G#===|",
+                "   |    1     |    2     |    3    |    4    |",
+            ]
+        );
+    }
 }
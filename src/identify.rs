@@ -0,0 +1,171 @@
+//! Reverse lookup of the scales that contain a set of notes, such as a few notes transcribed
+//! from a lick.
+
+use std::collections::HashSet;
+use strum::IntoEnumIterator;
+
+use crate::{
+    notes::{Note, NUM_NOTES},
+    scales::Scale,
+    spelling::{spell_root, spell_scale, SpelledNote, Spelling},
+};
+
+/// A root and built-in scale whose notes contain the notes looked up.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ScaleMatch {
+    pub root: Note,
+    pub scale: Scale,
+    /// Whether the scale has no notes besides the ones looked up.
+    pub exact: bool,
+}
+
+impl ScaleMatch {
+    /// The notes of the scale, spelled the way the scale is written.
+    pub fn spelled_notes(&self) -> Vec<String> {
+        let degrees = self.scale.get_degrees();
+        let spelling = Spelling::Scale(spell_scale(spell_root(self.root, &degrees), &degrees));
+        self.scale
+            .get_notes(self.root)
+            .into_iter()
+            .map(|(note, _)| spelling.name(note))
+            .collect()
+    }
+
+    fn root_name(&self) -> String {
+        self.spelled_notes().swap_remove(0)
+    }
+}
+
+/// Every root and built-in scale containing all of `notes`. Exact matches come first, then the
+/// scales with the fewest other notes, each in the order of [`Scale`] and then of the roots
+/// starting with the first note looked up.
+pub fn identify(notes: &[Note]) -> Vec<ScaleMatch> {
+    let wanted: HashSet<Note> = notes.iter().copied().collect();
+    let first = notes.first().copied().unwrap_or(Note::C);
+    let mut matches: Vec<(usize, ScaleMatch)> = Scale::iter()
+        .flat_map(|scale| (0..NUM_NOTES).map(move |semitones| (first.transpose(semitones), scale)))
+        .filter_map(|(root, scale)| {
+            let scale_notes: HashSet<Note> = scale
+                .get_notes(root)
+                .into_iter()
+                .map(|(note, _)| note)
+                .collect();
+            wanted.is_subset(&scale_notes).then(|| {
+                let others = scale_notes.len() - wanted.len();
+                let exact = others == 0;
+                (others, ScaleMatch { root, scale, exact })
+            })
+        })
+        .collect();
+    // The sort is stable, so equally close matches keep the order they were found in.
+    matches.sort_by_key(|(others, _)| *others);
+    matches.into_iter().map(|(_, found)| found).collect()
+}
+
+/// Lists the matches one per line with their notes, exact matches first under their own heading.
+pub fn render_matches(notes: &[SpelledNote], matches: &[ScaleMatch]) -> String {
+    let notes: Vec<String> = notes.iter().map(SpelledNote::to_string).collect();
+    if matches.is_empty() {
+        return format!("No scale contains {}\n", notes.join(", "));
+    }
+    let names: Vec<String> = matches
+        .iter()
+        .map(|found| format!("{} {}", found.root_name(), found.scale))
+        .collect();
+    let width = names.iter().map(String::len).max().unwrap_or_default();
+
+    let mut output = String::new();
+    let mut exact = None;
+    for (found, name) in matches.iter().zip(&names) {
+        if exact != Some(found.exact) {
+            if exact.is_some() {
+                output.push('\n');
+            }
+            output.push_str(&format!(
+                "{} {}:\n",
+                if found.exact {
+                    "Scales made of exactly"
+                } else {
+                    "Scales containing"
+                },
+                notes.join(", ")
+            ));
+            exact = Some(found.exact);
+        }
+        output.push_str(&format!(
+            "  {:<width$}  {}\n",
+            name,
+            found.spelled_notes().join(", ")
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(matches: &[ScaleMatch]) -> Vec<String> {
+        matches
+            .iter()
+            .map(|found| format!("{} {}", found.root_name(), found.scale))
+            .collect()
+    }
+
+    #[test]
+    fn test_identify() {
+        let matches = identify(&[Note::C, Note::E, Note::G, Note::ASharp, Note::D]);
+        assert!(matches.iter().all(|found| !found.exact));
+        assert_eq!(
+            names(&matches[..3]),
+            vec!["F Major", "F Melodic Minor", "G Melodic Minor"]
+        );
+        assert!(names(&matches).contains(&String::from("C Mixolydian")));
+        assert!(!names(&matches).contains(&String::from("C Major")));
+
+        let matches = identify(&[Note::A, Note::C, Note::D, Note::E, Note::G]);
+        let exact: Vec<String> = names(&matches)
+            .into_iter()
+            .zip(&matches)
+            .filter(|(_, found)| found.exact)
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(
+            exact,
+            vec![
+                "C Pentatonic Major",
+                "A Pentatonic Minor",
+                "D Pentatonic Neutral"
+            ]
+        );
+        assert!(!matches[3].exact);
+
+        // A single note is in every scale once for every note of the scale.
+        assert_eq!(
+            identify(&[Note::FSharp]).len(),
+            Scale::iter()
+                .map(|scale| scale.get_steps().len())
+                .sum::<usize>()
+        );
+        assert!(identify(&[Note::C, Note::CSharp, Note::D, Note::DSharp]).is_empty());
+    }
+
+    #[test]
+    fn test_render_matches() {
+        let notes: Vec<SpelledNote> = ["A", "C", "D", "E", "G"]
+            .iter()
+            .map(|name| name.parse().unwrap())
+            .collect();
+        let output = render_matches(
+            &notes,
+            &identify(&[Note::A, Note::C, Note::D, Note::E, Note::G]),
+        );
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "Scales made of exactly A, C, D, E, G:");
+        assert!(lines[1].starts_with("  C Pentatonic Major"));
+        assert!(lines[1].ends_with("  C, D, E, G, A"));
+        assert_eq!(lines[4], "");
+        assert_eq!(lines[5], "Scales containing A, C, D, E, G:");
+        assert_eq!(render_matches(&notes[..1], &[]), "No scale contains A\n");
+    }
+}
@@ -0,0 +1,199 @@
+//! Machine-readable description of a practice session.
+//!
+//! The JSON document has the following shape. Fields are only ever added within a schema
+//! version; renaming, removing or changing the meaning of a field bumps [`SCHEMA_VERSION`].
+//!
+//! ```json
+//! {
+//!   "schema_version": 1,
+//!   "tuning": {
+//!     "name": "Standard E (6 string)",
+//!     "strings": ["E", "A", "D", "G", "B", "E"],
+//!     "pitches": ["E2", "A2", "D3", "G3", "B3", "E4"]
+//!   },
+//!   "root": "D",
+//!   "scale": {
+//!     "name": "Phrygian",
+//!     "notes": [{ "note": "D", "step": 0, "degree": "1" }, { "note": "Eb", "step": 1, "degree": "b2" }]
+//!   },
+//!   "starting_fret": 6,
+//!   "positions": [
+//!     { "string": 6, "fret": 6, "note": "Bb", "pitch": "A#2", "midi": 46, "step": 8, "degree": "b6" }
+//!   ]
+//! }
+//! ```
+//!
+//! - `tuning.strings` lists the open strings from the lowest to the highest, and
+//!   `tuning.pitches` the same strings with their octave.
+//! - `pitch` is the sounding pitch of a position in scientific pitch notation, always spelled
+//!   with sharps, and `midi` its MIDI note number.
+//! - `step` is the number of half steps from the root, `degree` the scale degree such as `b3`.
+//! - `positions` holds every scale note inside the fret window. Strings are numbered the way
+//!   guitarists do, starting with 1 for the highest string.
+//! - `dimmed` is only present, and `true`, on scale notes outside the chord of an arpeggio.
+
+use serde::Serialize;
+
+use crate::{
+    fret_board::{FretBoard, Highlight},
+    notes::Note,
+    scales::ScaleSpec,
+    spelling::Spelling,
+    tunings::TuningSpec,
+};
+
+/// Version of the JSON document described in the module documentation.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize)]
+pub struct Session {
+    pub schema_version: u32,
+    pub tuning: TuningInfo,
+    pub root: String,
+    pub scale: ScaleInfo,
+    pub starting_fret: usize,
+    pub positions: Vec<PositionInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TuningInfo {
+    pub name: String,
+    pub strings: Vec<String>,
+    pub pitches: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScaleInfo {
+    pub name: String,
+    pub notes: Vec<ScaleNoteInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScaleNoteInfo {
+    pub note: String,
+    pub step: usize,
+    pub degree: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PositionInfo {
+    pub string: usize,
+    pub fret: usize,
+    pub note: String,
+    pub pitch: String,
+    pub midi: i32,
+    pub step: usize,
+    pub degree: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub dimmed: bool,
+}
+
+/// Describes the scale of `root` laid out on `fret_board`, naming notes with `spelling`.
+pub fn build_session(
+    tuning: &TuningSpec,
+    root: Note,
+    scale: &ScaleSpec,
+    fret_board: &FretBoard,
+    spelling: &Spelling,
+) -> Session {
+    let steps = scale.get_steps();
+    let degrees = scale.get_degrees();
+    let degree_of = |step: usize| {
+        steps
+            .iter()
+            .position(|scale_step| *scale_step == step)
+            .map(|index| degrees[index].to_string())
+            .unwrap_or_default()
+    };
+    let num_strings = fret_board.strings.len();
+
+    Session {
+        schema_version: SCHEMA_VERSION,
+        tuning: TuningInfo {
+            name: tuning.to_string(),
+            strings: tuning
+                .get_notes()
+                .iter()
+                .map(|note| String::from(note.to_str(false)))
+                .collect(),
+            pitches: tuning
+                .get_pitches()
+                .iter()
+                .map(|pitch| pitch.to_string())
+                .collect(),
+        },
+        root: spelling.name(root),
+        scale: ScaleInfo {
+            name: scale.to_string(),
+            notes: scale
+                .get_notes(root)
+                .into_iter()
+                .map(|(note, step)| ScaleNoteInfo {
+                    note: spelling.name(note),
+                    step,
+                    degree: degree_of(step),
+                })
+                .collect(),
+        },
+        starting_fret: fret_board.starting_fret,
+        positions: fret_board
+            .scale_positions()
+            .map(|(string_index, position)| {
+                let step = position.step.unwrap_or_default();
+                PositionInfo {
+                    string: num_strings - string_index,
+                    fret: position.fret,
+                    note: spelling.name(position.note),
+                    pitch: position.pitch.to_string(),
+                    midi: position.pitch.midi_number(),
+                    step,
+                    degree: degree_of(step),
+                    dimmed: position.highlight == Highlight::Dimmed,
+                }
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fret_board::{build_fret_board, FRET_SPAN},
+        scales::Scale,
+        tunings::Tuning,
+    };
+
+    #[test]
+    fn test_build_session() {
+        let tuning = TuningSpec::Preset(Tuning::StandardE6);
+        let scale = ScaleSpec::Preset(Scale::PentatonicMinor);
+        let fret_board = build_fret_board(
+            &tuning.get_pitches(),
+            5,
+            FRET_SPAN,
+            &scale.get_notes(Note::A),
+        );
+        let session = build_session(&tuning, Note::A, &scale, &fret_board, &Spelling::Sharps);
+        let json = serde_json::to_value(&session).unwrap();
+
+        assert_eq!(json["schema_version"], 1);
+        assert_eq!(json["tuning"]["strings"][0], "E");
+        assert_eq!(json["tuning"]["pitches"][5], "E4");
+        assert_eq!(json["scale"]["notes"][1]["note"], "C");
+        assert_eq!(json["scale"]["notes"][1]["degree"], "b3");
+        assert_eq!(
+            json["positions"][0],
+            serde_json::json!({
+                "string": 6,
+                "fret": 5,
+                "note": "A",
+                "pitch": "A2",
+                "midi": 45,
+                "step": 0,
+                "degree": "1"
+            })
+        );
+        assert_eq!(session.positions.len(), 13);
+    }
+}
@@ -0,0 +1,42 @@
+//! Building blocks of the `daily-scale` practice tool.
+//!
+//! Scales and tunings are looked up through [`scales::Scale`] and [`tunings::Tuning`],
+//! notes are handled through [`notes::Note`], and [`fret_board::build_fret_board`] lays a
+//! scale out over the neck. The resulting [`fret_board::FretBoard`] can be inspected
+//! directly or rendered as text with [`fret_board::render_fret_board`].
+//!
+//! ```
+//! use daily_scale::{
+//!     fret_board::{build_fret_board, FRET_SPAN},
+//!     notes::Note,
+//!     scales::Scale,
+//!     tunings::Tuning,
+//! };
+//!
+//! let tuning: Tuning = "standard-e6".parse().unwrap();
+//! let scale: Scale = "pentatonic-minor".parse().unwrap();
+//! let notes = scale.get_notes(Note::A);
+//! let fret_board = build_fret_board(&tuning.get_pitches(), 5, FRET_SPAN, &notes);
+//! assert_eq!(fret_board.scale_positions().count(), 13);
+//! ```
+
+pub mod arpeggio;
+pub mod caged;
+pub mod compare;
+pub mod format;
+pub mod fret_board;
+pub mod identify;
+pub mod json;
+pub mod midi;
+pub mod notes;
+pub mod pitch;
+pub mod playback;
+pub mod quiz;
+pub mod random;
+pub mod scales;
+pub mod spelling;
+pub mod svg;
+pub mod tab;
+pub mod three_nps;
+pub mod tunings;
+pub mod wav;
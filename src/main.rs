@@ -1,25 +1,377 @@
 mod cli;
-mod fret_board;
-mod notes;
-mod scales;
-mod tunings;
+mod config;
+mod plan;
+mod practice_log;
+mod selection;
+
+use chrono::Days;
+use rand::rng;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+use daily_scale::{
+    arpeggio::{chord_tones, mark_arpeggio},
+    caged::build_caged_fret_board,
+    compare::{build_comparison_fret_board, degree_differences, format_difference},
+    format::{format_with_color, Format},
+    fret_board::{build_fret_board, render_fret_board, FretBoard},
+    identify::{identify, render_matches},
+    json::build_session,
+    midi::render_midi,
+    notes::{Accidental, Note},
+    playback::up_and_down,
+    quiz::{ask_questions, hide_scale_notes, pick_questions},
+    scales::ScaleSpec,
+    spelling::{spell_root, spell_scale, SpelledNote, Spelling},
+    svg::render_svg,
+    tab::{render_tab, tab_run},
+    three_nps::build_three_nps_fret_board,
+    wav::render_wav,
+};
 
 use crate::{
-    cli::{get_params, print_output, Params},
-    fret_board::build_fret_board,
+    cli::{get_params, text_output, Action, Fingering, OutputFormat, Params},
+    plan::{render_plan, PlanFormat},
+    practice_log::{
+        append_entry, compute_stats, log_path, read_log, render_stats, write_log, LogEntry,
+    },
+    selection::Schedule,
 };
 
 fn main() {
     let params = get_params();
+    match params.action {
+        Some(Action::Done { tempo, ref notes }) => record_session(&params, tempo, notes.clone()),
+        Some(Action::Rate { grade }) => rate_session(&params, grade),
+        Some(Action::Plan { format, .. }) => show_plan(&params, format),
+        Some(Action::Identify { ref notes }) => show_matches(notes),
+        Some(Action::Compare {
+            ref first,
+            ref second,
+            root,
+        }) => show_comparison(&params, first, second, root),
+        Some(Action::Stats) => show_stats(&params),
+        None => match params.quiz {
+            Some(questions) => run_quiz(&params, questions),
+            None => show_scale(&params),
+        },
+    }
+}
+
+fn show_scale(params: &Params) {
+    let Params {
+        ref tuning,
+        root_note,
+        ref scale,
+        ref format,
+        output,
+        ref out_file,
+        ref midi,
+        tempo,
+        note_value,
+        ref wav,
+        count_in,
+        a4,
+        tab,
+        tab_root_string,
+        repeat_top,
+        ..
+    } = *params;
+
+    let fret_board = scale_fret_board(params);
+
+    let output = match output {
+        OutputFormat::Text => {
+            let mut output = text_output(params, render_fret_board(&fret_board, format));
+            if tab {
+                let run = tab_run(&fret_board, tab_root_string, repeat_top)
+                    .unwrap_or_else(|error| exit_with_error(&error));
+                output.push('\n');
+                for line in render_tab(&fret_board, &run) {
+                    output.push_str(&line);
+                    output.push('\n');
+                }
+            }
+            output
+        }
+        OutputFormat::Json => {
+            let session = build_session(tuning, root_note, scale, &fret_board, &format.spelling);
+            serde_json::to_string_pretty(&session).unwrap() + "\n"
+        }
+        OutputFormat::Svg => render_svg(&fret_board, format),
+    };
+
+    if midi.is_some() || wav.is_some() {
+        let pitches = up_and_down(&fret_board);
+        if let Some(path) = midi {
+            write_file(path, render_midi(&pitches, tempo, note_value));
+        }
+        if let Some(path) = wav {
+            write_file(path, render_wav(&pitches, tempo, note_value, count_in, a4));
+        }
+    }
+
+    match out_file {
+        Some(path) => write_file(path, output),
+        None => print!("{}", output),
+    }
+}
+
+/// The fret board of the scale of the day, laid out in its fingering.
+fn scale_fret_board(params: &Params) -> FretBoard {
     let Params {
-        tuning,
+        ref tuning,
+        root_note,
         starting_fret,
+        fret_span,
+        fingering,
         ref notes_in_scale,
-        ref format,
+        arpeggio,
         ..
-    } = params;
+    } = *params;
+
+    let pitches = tuning.get_pitches();
+    let mut fret_board = match fingering {
+        Some(Fingering::Caged(shape)) => {
+            build_caged_fret_board(&pitches, root_note, shape, notes_in_scale)
+        }
+        Some(Fingering::ThreeNps(degree)) => {
+            build_three_nps_fret_board(&pitches, notes_in_scale, degree)
+        }
+        None => Ok(build_fret_board(
+            &pitches,
+            starting_fret,
+            fret_span,
+            notes_in_scale,
+        )),
+    }
+    .unwrap_or_else(|error| exit_with_error(&error));
+    if let Some(degree) = arpeggio {
+        let chord_tones =
+            chord_tones(notes_in_scale, degree).unwrap_or_else(|error| exit_with_error(&error));
+        mark_arpeggio(&mut fret_board, &chord_tones);
+    }
+    fret_board
+}
+
+/// Quizzes the player on the notes of the scale, showing only the roots, and reveals the whole
+/// scale at the end.
+fn run_quiz(params: &Params, num_questions: usize) {
+    let fret_board = scale_fret_board(params);
+    let mut blank_board = fret_board.clone();
+    hide_scale_notes(&mut blank_board);
+    for line in render_fret_board(&blank_board, &params.format) {
+        println!("{}", line);
+    }
+    println!(
+        "Quiz on {} {} starting at fret {} in {} tuning",
+        params.format.spelling.name(params.root_note),
+        params.scale,
+        fret_board.starting_fret,
+        params.tuning,
+    );
+    println!("Name each note, e.g. F# or Gb, or its interval from the root, e.g. b3\n");
+
+    let questions = pick_questions(&mut rng(), &blank_board, num_questions);
+    let score = ask_questions(
+        &blank_board,
+        &questions,
+        &params.format,
+        io::stdin().lock(),
+        io::stdout(),
+    )
+    .unwrap_or_else(|error| exit_with_error(&error.to_string()));
+    println!("\nYou got {} of {} right\n", score, questions.len());
+    for line in render_fret_board(&fret_board, &params.format) {
+        println!("{}", line);
+    }
+}
+
+fn record_session(params: &Params, tempo: Option<u32>, notes: Option<String>) {
+    let path = practice_log_path(params);
+    let entry = LogEntry {
+        tempo,
+        notes,
+        ..todays_entry(params)
+    };
+    let entries = append_entry(&path, &entry).and_then(|_| read_log(&path));
+    match entries {
+        Ok(entries) => {
+            let stats = compute_stats(&entries, &params.candidates, params.date);
+            println!(
+                "Logged {} {} starting at fret {} in {} tuning. Current streak: {} day{}",
+                params.format.spelling.name(params.root_note),
+                params.scale,
+                params.starting_fret,
+                params.tuning,
+                stats.current_streak,
+                if stats.current_streak == 1 { "" } else { "s" },
+            );
+        }
+        Err(error) => exit_with_error(&error),
+    }
+}
+
+/// Rates today's session, logging it first if `done` wasn't run yet.
+fn rate_session(params: &Params, grade: u8) {
+    let path = practice_log_path(params);
+    let mut entries = read_log(&path).unwrap_or_else(|error| exit_with_error(&error));
+    let today = todays_entry(params);
+    let logged = entries.iter_mut().rev().find(|entry| {
+        entry.date == today.date
+            && entry.tuning == today.tuning
+            && entry.root == today.root
+            && entry.scale == today.scale
+    });
+    match logged {
+        Some(entry) => entry.rating = Some(grade),
+        None => entries.push(LogEntry {
+            rating: Some(grade),
+            ..today
+        }),
+    }
+    if let Err(error) = write_log(&path, &entries) {
+        exit_with_error(&error);
+    }
+
+    let tomorrow = params
+        .date
+        .checked_add_days(Days::new(1))
+        .unwrap_or_else(|| exit_with_error("there is no day after the last supported date"));
+    let schedule = Schedule::new(&entries, &params.tuning.key(), tomorrow);
+    let review = schedule
+        .review(params.root_note, &params.scale)
+        .expect("the session rated above is always scheduled");
+    println!(
+        "Rated {} {} in {} tuning {}/5. Next review on {} (in {} day{})",
+        params.format.spelling.name(params.root_note),
+        params.scale,
+        params.tuning,
+        grade,
+        review.due,
+        review.interval,
+        if review.interval == 1 { "" } else { "s" },
+    );
+}
+
+/// A log entry for the scale of the day.
+fn todays_entry(params: &Params) -> LogEntry {
+    LogEntry {
+        date: params.date,
+        tuning: params.tuning.key(),
+        root: String::from(params.root_note.to_str(false)),
+        scale: params.scale.key(),
+        starting_fret: params.starting_fret,
+        tempo: None,
+        notes: None,
+        rating: None,
+    }
+}
+
+fn show_stats(params: &Params) {
+    match read_log(&practice_log_path(params)) {
+        Ok(entries) => print!(
+            "{}",
+            render_stats(&compute_stats(&entries, &params.candidates, params.date,))
+        ),
+        Err(error) => exit_with_error(&error),
+    }
+}
 
-    let fret_board = build_fret_board(tuning, starting_fret, notes_in_scale, format);
+/// Draws both scales over the fret window of the day and lists the degrees they differ in.
+fn show_comparison(
+    params: &Params,
+    first: &ScaleSpec,
+    second: &ScaleSpec,
+    root: Option<Accidental>,
+) {
+    let root_note = root.map_or(params.root_note, Accidental::to_note);
+    let spelled_root = root
+        .map(SpelledNote::from)
+        .unwrap_or_else(|| spell_root(root_note, &first.get_degrees()));
+    let first_notes = spell_scale(spelled_root, &first.get_degrees());
+    let second_notes = spell_scale(spelled_root, &second.get_degrees());
+    let format = Format {
+        spelling: Spelling::Scale([first_notes.clone(), second_notes.clone()].concat()),
+        colored: params.format.colored,
+        labels: params.format.labels,
+        degrees: [first.get_degrees(), second.get_degrees()].concat(),
+    };
+    let fret_board = build_comparison_fret_board(
+        &params.tuning.get_pitches(),
+        params.starting_fret,
+        params.fret_span,
+        root_note,
+        first,
+        second,
+    );
+
+    for line in render_fret_board(&fret_board, &format) {
+        println!("{}", line);
+    }
+    println!(
+        "Comparing {} {} and {} {} starting at fret {} in {} tuning",
+        spelled_root, first, spelled_root, second, params.starting_fret, params.tuning
+    );
+    let name_width = first.to_string().len().max(second.to_string().len()) + 1;
+    for (scale, notes) in [(first, &first_notes), (second, &second_notes)] {
+        println!(
+            "{:<name_width$} {}",
+            format!("{}:", scale),
+            notes
+                .iter()
+                .zip(scale.get_steps())
+                .map(|(note, step)| format_with_color(&note.to_string(), *step, format.colored))
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+    }
+    let differences = degree_differences(first, second);
+    if differences.is_empty() {
+        println!("Both scales have the same notes");
+    } else {
+        println!(
+            "Notes only in {} are in (parentheses), notes only in {} in [brackets]",
+            first, second
+        );
+        println!(
+            "Differences: {}",
+            differences
+                .iter()
+                .map(format_difference)
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+    }
+}
+
+fn show_matches(notes: &[SpelledNote]) {
+    let matches = identify(&notes.iter().map(SpelledNote::note).collect::<Vec<Note>>());
+    print!("{}", render_matches(notes, &matches));
+}
+
+fn show_plan(params: &Params, format: PlanFormat) {
+    let output = render_plan(&params.plan, format);
+    match params.out_file {
+        Some(ref path) => write_file(path, output),
+        None => print!("{}", output),
+    }
+}
+
+fn practice_log_path(params: &Params) -> PathBuf {
+    log_path(params.log_file.as_deref()).unwrap_or_else(|error| exit_with_error(&error))
+}
+
+fn exit_with_error(error: &str) -> ! {
+    eprintln!("error: {}", error);
+    process::exit(1);
+}
 
-    print_output(params, fret_board);
+fn write_file(path: &Path, contents: impl AsRef<[u8]>) {
+    if let Err(error) = fs::write(path, contents) {
+        exit_with_error(&format!("could not write {}: {}", path.display(), error));
+    }
 }
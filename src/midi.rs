@@ -0,0 +1,99 @@
+use crate::{pitch::Pitch, playback::NoteValue};
+
+/// Resolution of the MIDI file, in ticks per quarter note.
+const TICKS_PER_QUARTER: u32 = 480;
+/// General MIDI program of the acoustic guitar (nylon), counted from zero.
+const GUITAR_PROGRAM: u8 = 24;
+const VELOCITY: u8 = 80;
+
+/// Writes the pitches as a single track Standard MIDI File, one after another at `tempo`
+/// quarter notes per minute, each lasting `note_value`.
+pub fn render_midi(pitches: &[Pitch], tempo: u32, note_value: NoteValue) -> Vec<u8> {
+    let duration = TICKS_PER_QUARTER * 4 / note_value.divisions();
+    let microseconds_per_quarter = 60_000_000 / tempo.max(1);
+
+    let mut track = Vec::new();
+    // Tempo meta event.
+    write_variable_length(&mut track, 0);
+    track.extend_from_slice(&[0xFF, 0x51, 0x03]);
+    track.extend_from_slice(&microseconds_per_quarter.to_be_bytes()[1..]);
+    // Program change on channel 1.
+    write_variable_length(&mut track, 0);
+    track.extend_from_slice(&[0xC0, GUITAR_PROGRAM]);
+    for pitch in pitches {
+        let key = pitch.midi_number().clamp(0, 127) as u8;
+        write_variable_length(&mut track, 0);
+        track.extend_from_slice(&[0x90, key, VELOCITY]);
+        write_variable_length(&mut track, duration);
+        track.extend_from_slice(&[0x80, key, 0]);
+    }
+    // End of track meta event.
+    write_variable_length(&mut track, 0);
+    track.extend_from_slice(&[0xFF, 0x2F, 0x00]);
+
+    let mut midi = Vec::with_capacity(track.len() + 22);
+    midi.extend_from_slice(b"MThd");
+    midi.extend_from_slice(&6u32.to_be_bytes());
+    // Format 0, one track.
+    midi.extend_from_slice(&0u16.to_be_bytes());
+    midi.extend_from_slice(&1u16.to_be_bytes());
+    midi.extend_from_slice(&(TICKS_PER_QUARTER as u16).to_be_bytes());
+    midi.extend_from_slice(b"MTrk");
+    midi.extend_from_slice(&(track.len() as u32).to_be_bytes());
+    midi.extend_from_slice(&track);
+    midi
+}
+
+/// Appends `value` as a MIDI variable-length quantity, seven bits per byte, most significant
+/// first.
+fn write_variable_length(bytes: &mut Vec<u8>, value: u32) {
+    let mut groups = vec![(value & 0x7F) as u8];
+    let mut rest = value >> 7;
+    while rest > 0 {
+        groups.push((rest & 0x7F) as u8 | 0x80);
+        rest >>= 7;
+    }
+    bytes.extend(groups.iter().rev());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notes::Note;
+
+    #[test]
+    fn test_write_variable_length() {
+        let encode = |value: u32| {
+            let mut bytes = Vec::new();
+            write_variable_length(&mut bytes, value);
+            bytes
+        };
+        assert_eq!(encode(0), vec![0x00]);
+        assert_eq!(encode(0x7F), vec![0x7F]);
+        assert_eq!(encode(0x80), vec![0x81, 0x00]);
+        assert_eq!(encode(480), vec![0x83, 0x60]);
+        assert_eq!(encode(0x0FFF_FFFF), vec![0xFF, 0xFF, 0xFF, 0x7F]);
+    }
+
+    #[test]
+    fn test_render_midi() {
+        let pitches = [Pitch::new(Note::E, 2), Pitch::new(Note::A, 4)];
+        let midi = render_midi(&pitches, 120, NoteValue::Eighth);
+
+        assert_eq!(&midi[..4], b"MThd");
+        assert_eq!(&midi[12..14], &[0x01, 0xE0]);
+        assert_eq!(&midi[14..18], b"MTrk");
+        let track = &midi[22..];
+        assert_eq!(
+            track.len() as u32,
+            u32::from_be_bytes(midi[18..22].try_into().unwrap())
+        );
+        // 500000 microseconds per quarter note at 120 BPM.
+        assert_eq!(&track[..7], &[0x00, 0xFF, 0x51, 0x03, 0x07, 0xA1, 0x20]);
+        assert_eq!(&track[10..14], &[0x00, 0x90, 40, VELOCITY]);
+        // An eighth note lasts 240 ticks.
+        assert_eq!(&track[14..18], &[0x81, 0x70, 0x80, 40]);
+        assert_eq!(&track[19..23], &[0x00, 0x90, 69, VELOCITY]);
+        assert!(track.ends_with(&[0x00, 0xFF, 0x2F, 0x00]));
+    }
+}
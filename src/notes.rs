@@ -1,27 +1,40 @@
 use clap::ValueEnum;
-use std::collections::HashSet;
-use strum::EnumIter;
+use std::{collections::HashSet, str::FromStr};
+use strum::{EnumIter, IntoEnumIterator};
 
+/// Number of notes in an octave.
 pub const NUM_NOTES: usize = 12;
 
+/// A root note as spelled on the command line, keeping track of sharps and flats. Besides the
+/// names like `b-flat`, the note names as written such as `bb` or `f#` are accepted.
 #[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, Hash)]
 pub enum Accidental {
+    #[value(alias = "ab")]
     AFlat,
     A,
+    #[value(alias = "a#")]
     ASharp,
+    #[value(alias = "bb")]
     BFlat,
     B,
     C,
+    #[value(alias = "c#")]
     CSharp,
+    #[value(alias = "db")]
     DFlat,
     D,
+    #[value(alias = "d#")]
     DSharp,
+    #[value(alias = "eb")]
     EFlat,
     E,
     F,
+    #[value(alias = "f#")]
     FSharp,
+    #[value(alias = "gb")]
     GFlat,
     G,
+    #[value(alias = "g#")]
     GSharp,
 }
 
@@ -49,7 +62,8 @@ impl Accidental {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, EnumIter)]
+/// One of the twelve pitch classes, without octave information.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, EnumIter)]
 pub enum Note {
     A,
     ASharp,
@@ -66,6 +80,27 @@ pub enum Note {
 }
 
 impl Note {
+    /// Position of the note in the chromatic scale starting from `A`.
+    pub fn index(self) -> usize {
+        Note::iter().position(|note| note == self).unwrap()
+    }
+
+    /// Inverse of [`Note::index`]; the index wraps around the octave.
+    pub fn from_index(index: usize) -> Note {
+        Note::iter().nth(index % NUM_NOTES).unwrap()
+    }
+
+    /// The note `semitones` half steps above this one.
+    pub fn transpose(self, semitones: usize) -> Note {
+        Note::from_index(self.index() + semitones)
+    }
+
+    /// Number of half steps going up from this note to `other`, in `0..NUM_NOTES`.
+    pub fn interval_to(self, other: Note) -> usize {
+        (other.index() + NUM_NOTES - self.index()) % NUM_NOTES
+    }
+
+    /// Name of the note, spelled with flats if `flat` is set and sharps otherwise.
     pub fn to_str(self, flat: bool) -> &'static str {
         match self {
             Note::A => "A",
@@ -114,6 +149,31 @@ impl Note {
     }
 }
 
+impl FromStr for Note {
+    type Err = String;
+
+    /// Parses a note name such as `E`, `F#` or `Bb`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.trim().chars();
+        let natural = match chars.next().map(|letter| letter.to_ascii_uppercase()) {
+            Some('A') => Note::A,
+            Some('B') => Note::B,
+            Some('C') => Note::C,
+            Some('D') => Note::D,
+            Some('E') => Note::E,
+            Some('F') => Note::F,
+            Some('G') => Note::G,
+            _ => return Err(format!("Invalid note name: '{}'", s.trim())),
+        };
+        chars.try_fold(natural, |note, accidental| match accidental {
+            '#' => Ok(note.transpose(1)),
+            'b' => Ok(note.transpose(NUM_NOTES - 1)),
+            _ => Err(format!("Invalid note name: '{}'", s.trim())),
+        })
+    }
+}
+
+/// The accidentals that should be spelled with flats.
 pub fn get_flat_accidentals() -> HashSet<Accidental> {
     [
         Accidental::AFlat,
@@ -136,6 +196,23 @@ mod tests {
         assert_eq!(Accidental::AFlat.to_note(), Note::GSharp);
     }
 
+    #[test]
+    fn test_note_arithmetic() {
+        assert_eq!(Note::A.index(), 0);
+        assert_eq!(Note::from_index(15), Note::C);
+        assert_eq!(Note::G.transpose(5), Note::C);
+        assert_eq!(Note::E.interval_to(Note::D), 10);
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("E".parse::<Note>(), Ok(Note::E));
+        assert_eq!("db".parse::<Note>(), Ok(Note::CSharp));
+        assert_eq!("Cb".parse::<Note>(), Ok(Note::B));
+        assert!("H".parse::<Note>().is_err());
+        assert!("C$".parse::<Note>().is_err());
+    }
+
     #[test]
     fn test_to_string() {
         assert_eq!(Note::CSharp.to_str(true), "Db");
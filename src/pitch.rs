@@ -0,0 +1,193 @@
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use crate::notes::{Note, NUM_NOTES};
+
+/// A note in a specific octave, in scientific pitch notation where middle C is `C4`.
+///
+/// Pitches are ordered from low to high.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Pitch {
+    pub note: Note,
+    pub octave: i32,
+}
+
+/// Default frequency of `A4` in Hz, the reference every other pitch is tuned from.
+pub const A4_FREQUENCY: f64 = 440.0;
+
+/// Lowest MIDI number given to the lowest open string when octaves are inferred (`A1`).
+const LOWEST_OPEN_STRING: i32 = 33;
+
+impl Pitch {
+    pub fn new(note: Note, octave: i32) -> Pitch {
+        Pitch { note, octave }
+    }
+
+    /// The pitch with the given MIDI note number, where 60 is `C4`.
+    pub fn from_midi_number(midi_number: i32) -> Pitch {
+        Pitch {
+            note: Note::C.transpose(midi_number.rem_euclid(NUM_NOTES as i32) as usize),
+            octave: midi_number.div_euclid(NUM_NOTES as i32) - 1,
+        }
+    }
+
+    /// MIDI note number of the pitch, where 60 is `C4`.
+    pub fn midi_number(&self) -> i32 {
+        (self.octave + 1) * NUM_NOTES as i32 + Note::C.interval_to(self.note) as i32
+    }
+
+    /// Frequency of the pitch in Hz, in equal temperament with `A4` tuned to `a4` Hz.
+    pub fn frequency(&self, a4: f64) -> f64 {
+        a4 * 2f64.powf((self.midi_number() - 69) as f64 / NUM_NOTES as f64)
+    }
+
+    /// The pitch `semitones` half steps higher.
+    pub fn transpose(&self, semitones: usize) -> Pitch {
+        Pitch::from_midi_number(self.midi_number() + semitones as i32)
+    }
+}
+
+impl Ord for Pitch {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.midi_number().cmp(&other.midi_number())
+    }
+}
+
+impl PartialOrd for Pitch {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Pitch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.note.to_str(false), self.octave)
+    }
+}
+
+impl FromStr for Pitch {
+    type Err = String;
+
+    /// Parses a pitch such as `E2`, `F#3` or `Bb-1`. The octave belongs to the letter, so `B#3`
+    /// is the same pitch as `C4`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || format!("Invalid pitch: '{}'", s);
+        let octave_start = s
+            .char_indices()
+            .skip(1)
+            .find(|(_, c)| c.is_ascii_digit() || *c == '-')
+            .map(|(index, _)| index)
+            .ok_or_else(invalid)?;
+        let (name, octave) = s.split_at(octave_start);
+        let octave = octave.parse::<i32>().map_err(|_| invalid())?;
+        let mut chars = name.chars();
+        let natural = chars
+            .next()
+            .map(String::from)
+            .ok_or_else(invalid)?
+            .parse::<Note>()
+            .map_err(|_| invalid())?;
+        let accidentals = chars.try_fold(0, |offset, accidental| match accidental {
+            '#' => Ok(offset + 1),
+            'b' => Ok(offset - 1),
+            _ => Err(invalid()),
+        })?;
+        Ok(Pitch::from_midi_number(
+            Pitch::new(natural, octave).midi_number() + accidentals,
+        ))
+    }
+}
+
+/// Gives octaves to the open strings of a tuning, from the lowest string to the highest.
+///
+/// The lowest string is placed between `A1` and `G#2`, where the low strings of 6 and 7 string
+/// guitars sit, and every other string is the closest pitch above the string below it.
+pub fn infer_open_pitches(tuning: &[Note]) -> Vec<Pitch> {
+    let mut pitches: Vec<Pitch> = Vec::with_capacity(tuning.len());
+    for note in tuning {
+        let pitch = match pitches.last() {
+            Some(previous) => {
+                let interval = previous.note.interval_to(*note);
+                previous.transpose(if interval == 0 { NUM_NOTES } else { interval })
+            }
+            None => {
+                let lowest = Pitch::from_midi_number(LOWEST_OPEN_STRING);
+                lowest.transpose(lowest.note.interval_to(*note))
+            }
+        };
+        pitches.push(pitch);
+    }
+    pitches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tunings::Tuning;
+
+    #[test]
+    fn test_midi_number() {
+        assert_eq!(Pitch::new(Note::C, 4).midi_number(), 60);
+        assert_eq!(Pitch::new(Note::A, 4).midi_number(), 69);
+        assert_eq!(Pitch::new(Note::E, 2).midi_number(), 40);
+        assert_eq!(Pitch::from_midi_number(35), Pitch::new(Note::B, 1));
+        assert_eq!(Pitch::new(Note::B, 3).transpose(1), Pitch::new(Note::C, 4));
+    }
+
+    #[test]
+    fn test_frequency() {
+        assert_eq!(Pitch::new(Note::A, 4).frequency(A4_FREQUENCY), 440.0);
+        assert_eq!(Pitch::new(Note::A, 2).frequency(A4_FREQUENCY), 110.0);
+        assert!((Pitch::new(Note::E, 2).frequency(A4_FREQUENCY) - 82.407).abs() < 0.001);
+        assert_eq!(Pitch::new(Note::A, 3).frequency(432.0), 216.0);
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("E2".parse::<Pitch>(), Ok(Pitch::new(Note::E, 2)));
+        assert_eq!("f#3".parse::<Pitch>(), Ok(Pitch::new(Note::FSharp, 3)));
+        assert_eq!("Bb1".parse::<Pitch>(), Ok(Pitch::new(Note::ASharp, 1)));
+        assert_eq!("B#3".parse::<Pitch>(), Ok(Pitch::new(Note::C, 4)));
+        assert_eq!("Cb4".parse::<Pitch>(), Ok(Pitch::new(Note::B, 3)));
+        assert_eq!("C-1".parse::<Pitch>().unwrap().midi_number(), 0);
+        assert!("E".parse::<Pitch>().is_err());
+        assert!("H2".parse::<Pitch>().is_err());
+        assert!("E2b".parse::<Pitch>().is_err());
+        assert_eq!(Pitch::new(Note::CSharp, 4).to_string(), "C#4");
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(Pitch::new(Note::B, 2) < Pitch::new(Note::C, 3));
+        assert!(Pitch::new(Note::A, 2) > Pitch::new(Note::GSharp, 2));
+        assert_eq!(
+            [Pitch::new(Note::E, 4), Pitch::new(Note::E, 2)]
+                .iter()
+                .max(),
+            Some(&Pitch::new(Note::E, 4))
+        );
+    }
+
+    #[test]
+    fn test_infer_open_pitches() {
+        assert_eq!(
+            infer_open_pitches(Tuning::StandardE6.get_notes()),
+            vec![
+                Pitch::new(Note::E, 2),
+                Pitch::new(Note::A, 2),
+                Pitch::new(Note::D, 3),
+                Pitch::new(Note::G, 3),
+                Pitch::new(Note::B, 3),
+                Pitch::new(Note::E, 4),
+            ]
+        );
+        assert_eq!(
+            infer_open_pitches(Tuning::DropA7.get_notes())[0],
+            Pitch::new(Note::A, 1)
+        );
+        assert_eq!(
+            infer_open_pitches(Tuning::OpenC6.get_notes())[4],
+            Pitch::new(Note::C, 4)
+        );
+    }
+}
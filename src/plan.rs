@@ -0,0 +1,235 @@
+use chrono::{Datelike, Days, NaiveDate};
+use clap::ValueEnum;
+
+use daily_scale::random::stable_hash;
+
+use crate::cli::Params;
+
+const MAX_PLAN_DAYS: u32 = 366;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum PlanFormat {
+    #[default]
+    Text,
+    Markdown,
+    Ics,
+}
+
+/// The scale picked for one day of a practice plan, with every name already spelled out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlanDay {
+    pub date: NaiveDate,
+    pub root: String,
+    pub scale: String,
+    pub tuning: String,
+    pub starting_fret: usize,
+}
+
+impl From<&Params> for PlanDay {
+    fn from(params: &Params) -> Self {
+        PlanDay {
+            date: params.date,
+            root: params.format.spelling.name(params.root_note),
+            scale: params.scale.to_string(),
+            tuning: params.tuning.to_string(),
+            starting_fret: params.starting_fret,
+        }
+    }
+}
+
+pub fn validate_plan_days(num: u32) -> Result<u32, String> {
+    if (1..=MAX_PLAN_DAYS).contains(&num) {
+        Ok(num)
+    } else {
+        Err(format!("Number must be between 1 and {}", MAX_PLAN_DAYS))
+    }
+}
+
+/// Parses a month written as `YYYY-MM` into its first day.
+pub fn parse_month(s: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(&format!("{}-01", s), "%Y-%m-%d")
+        .map_err(|_| String::from("Not a valid month, use YYYY-MM"))
+}
+
+/// `days` days starting with `start`.
+pub fn plan_dates(start: NaiveDate, days: u32) -> Vec<NaiveDate> {
+    start.iter_days().take(days as usize).collect()
+}
+
+/// Every day of the month that starts with `first`.
+pub fn month_dates(first: NaiveDate) -> Vec<NaiveDate> {
+    first
+        .iter_days()
+        .take_while(|date| date.month() == first.month())
+        .collect()
+}
+
+pub fn render_plan(days: &[PlanDay], format: PlanFormat) -> String {
+    match format {
+        PlanFormat::Text => render_text(days),
+        PlanFormat::Markdown => render_markdown(days),
+        PlanFormat::Ics => render_ics(days),
+    }
+}
+
+fn render_text(days: &[PlanDay]) -> String {
+    let width = |column: fn(&PlanDay) -> &str| {
+        days.iter()
+            .map(|day| column(day).chars().count())
+            .max()
+            .unwrap_or_default()
+    };
+    let root_width = width(|day| &day.root);
+    let scale_width = width(|day| &day.scale);
+    days.iter()
+        .map(|day| {
+            format!(
+                "{} {}  {:<root_width$}  {:<scale_width$}  fret {:<2}  {}\n",
+                day.date,
+                day.date.format("%a"),
+                day.root,
+                day.scale,
+                day.starting_fret,
+                day.tuning,
+            )
+        })
+        .collect()
+}
+
+fn render_markdown(days: &[PlanDay]) -> String {
+    let mut output = String::from(
+        "| Date | Root | Scale | Starting fret | Tuning |\n| --- | --- | --- | --- | --- |\n",
+    );
+    for day in days {
+        output.push_str(&format!(
+            "| {} {} | {} | {} | {} | {} |\n",
+            day.date,
+            day.date.format("%a"),
+            day.root,
+            day.scale.replace('|', "\\|"),
+            day.starting_fret,
+            day.tuning.replace('|', "\\|"),
+        ));
+    }
+    output
+}
+
+/// An iCalendar file with an all-day event for every day. The events are identified by their
+/// date and a hash of the tuning, root and scale, so importing the same plan again updates its
+/// events, while plans of other profiles or tunings on the same days are kept next to them.
+fn render_ics(days: &[PlanDay]) -> String {
+    let mut lines = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        String::from("PRODID:-//daily-scale//practice plan//EN"),
+        String::from("CALSCALE:GREGORIAN"),
+    ];
+    for day in days {
+        let next_day = day.date.checked_add_days(Days::new(1)).unwrap_or(day.date);
+        lines.extend([
+            String::from("BEGIN:VEVENT"),
+            format!(
+                "UID:{}-{:016x}@daily-scale",
+                day.date.format("%Y%m%d"),
+                stable_hash(&format!("{}|{}|{}", day.tuning, day.root, day.scale))
+            ),
+            format!("DTSTAMP:{}T000000Z", day.date.format("%Y%m%d")),
+            format!("DTSTART;VALUE=DATE:{}", day.date.format("%Y%m%d")),
+            format!("DTEND;VALUE=DATE:{}", next_day.format("%Y%m%d")),
+            format!(
+                "SUMMARY:{}",
+                escape_ics(&format!("{} {}", day.root, day.scale))
+            ),
+            format!(
+                "DESCRIPTION:{}",
+                escape_ics(&format!(
+                    "Starting at fret {} in {} tuning",
+                    day.starting_fret, day.tuning
+                ))
+            ),
+            String::from("END:VEVENT"),
+        ]);
+    }
+    lines.push(String::from("END:VCALENDAR"));
+    lines.iter().map(|line| line.clone() + "\r\n").collect()
+}
+
+fn escape_ics(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(date: &str, root: &str, scale: &str, starting_fret: usize) -> PlanDay {
+        PlanDay {
+            date: date.parse().unwrap(),
+            root: String::from(root),
+            scale: String::from(scale),
+            tuning: String::from("Standard E (6 string)"),
+            starting_fret,
+        }
+    }
+
+    #[test]
+    fn test_plan_dates() {
+        let start = "2026-10-30".parse().unwrap();
+        let dates = plan_dates(start, 3);
+        assert_eq!(dates.last(), Some(&"2026-11-01".parse().unwrap()));
+        let month = month_dates(parse_month("2028-02").unwrap());
+        assert_eq!(month.len(), 29);
+        assert_eq!(month[0], "2028-02-01".parse().unwrap());
+        assert!(parse_month("2028-13").is_err());
+        assert!(parse_month("2028-02-01").is_err());
+    }
+
+    #[test]
+    fn test_render_plan() {
+        let days = [
+            day("2026-10-18", "F", "Pentatonic Major", 7),
+            day("2026-10-19", "C#", "Dorian", 12),
+        ];
+        assert_eq!(
+            render_plan(&days, PlanFormat::Text),
+            "2026-10-18 Sun  F   Pentatonic Major  fret 7   Standard E (6 string)\n\
+             2026-10-19 Mon  C#  Dorian            fret 12  Standard E (6 string)\n"
+        );
+        let markdown = render_plan(&days, PlanFormat::Markdown);
+        assert_eq!(markdown.lines().count(), 4);
+        assert!(
+            markdown.contains("| 2026-10-19 Mon | C# | Dorian | 12 | Standard E (6 string) |\n")
+        );
+
+        let ics = render_plan(&days, PlanFormat::Ics);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains("DTSTART;VALUE=DATE:20261019\r\nDTEND;VALUE=DATE:20261020\r\n"));
+        assert!(ics.contains("SUMMARY:C# Dorian\r\n"));
+        assert!(ics.contains("DESCRIPTION:Starting at fret 12 in Standard E (6 string) tuning\r\n"));
+        assert_eq!(escape_ics("a,b;c\\d"), "a\\,b\\;c\\\\d");
+
+        // Plans of other profiles or tunings for the same day don't replace each other.
+        let uid = |day: PlanDay| {
+            render_plan(&[day], PlanFormat::Ics)
+                .lines()
+                .find(|line| line.starts_with("UID:"))
+                .map(String::from)
+                .unwrap()
+        };
+        let dorian = uid(day("2026-10-19", "C#", "Dorian", 12));
+        assert!(dorian.starts_with("UID:20261019-"));
+        assert_eq!(dorian, uid(day("2026-10-19", "C#", "Dorian", 5)));
+        assert_ne!(dorian, uid(day("2026-10-19", "C#", "Lydian", 12)));
+        assert_ne!(dorian, uid(day("2026-10-19", "D", "Dorian", 12)));
+        let drop_d = PlanDay {
+            tuning: String::from("Drop D (6 string)"),
+            ..day("2026-10-19", "C#", "Dorian", 12)
+        };
+        assert_ne!(dorian, uid(drop_d));
+    }
+}
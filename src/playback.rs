@@ -0,0 +1,101 @@
+use clap::ValueEnum;
+
+use crate::{
+    fret_board::{FretBoard, FretPosition, Highlight},
+    pitch::Pitch,
+};
+
+/// Tempo used for playback when none is given, in quarter notes per minute.
+pub const DEFAULT_TEMPO: u32 = 80;
+
+/// Length of every note when the scale is played back.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum NoteValue {
+    Whole,
+    Half,
+    #[default]
+    Quarter,
+    Eighth,
+    Sixteenth,
+}
+
+impl NoteValue {
+    /// How many notes of this value fit in a whole note.
+    pub fn divisions(&self) -> u32 {
+        match self {
+            NoteValue::Whole => 1,
+            NoteValue::Half => 2,
+            NoteValue::Quarter => 4,
+            NoteValue::Eighth => 8,
+            NoteValue::Sixteenth => 16,
+        }
+    }
+}
+
+/// The scale positions of the fret board in playing order, from the lowest string to the
+/// highest, with the index of their string.
+///
+/// Positions that are not higher than the note before them, such as the unison between the G
+/// and B strings, are skipped so the run keeps climbing. Dimmed positions are skipped too, so
+/// an arpeggio plays only its chord tones.
+pub fn ascending_positions(fret_board: &FretBoard) -> Vec<(usize, &FretPosition)> {
+    let mut run: Vec<(usize, &FretPosition)> = Vec::new();
+    for (string_index, position) in fret_board
+        .scale_positions()
+        .filter(|(_, position)| position.highlight != Highlight::Dimmed)
+    {
+        if run
+            .last()
+            .is_none_or(|(_, last)| position.pitch > last.pitch)
+        {
+            run.push((string_index, position));
+        }
+    }
+    run
+}
+
+/// Pitches of the scale played up the fret window and back down, without repeating the top note.
+pub fn up_and_down(fret_board: &FretBoard) -> Vec<Pitch> {
+    let ascending: Vec<Pitch> = ascending_positions(fret_board)
+        .into_iter()
+        .map(|(_, position)| position.pitch)
+        .collect();
+    let descending = ascending.iter().rev().skip(1).copied();
+    ascending.iter().copied().chain(descending).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fret_board::{build_fret_board, FRET_SPAN},
+        notes::Note,
+        scales::Scale,
+        tunings::Tuning,
+    };
+
+    #[test]
+    fn test_up_and_down() {
+        let fret_board = build_fret_board(
+            &Tuning::StandardE6.get_pitches(),
+            5,
+            FRET_SPAN,
+            &Scale::PentatonicMinor.get_notes(Note::A),
+        );
+
+        let ascending = ascending_positions(&fret_board);
+        assert_eq!(ascending.len(), 12);
+        assert_eq!(ascending[0].1.pitch, Pitch::new(Note::A, 2));
+        assert_eq!(ascending[11].1.pitch, Pitch::new(Note::C, 5));
+        // The E on the 5th fret of the B string was already played on the G string.
+        assert!(ascending
+            .iter()
+            .all(|(string_index, position)| (*string_index, position.fret) != (4, 5)));
+
+        let pitches = up_and_down(&fret_board);
+        assert_eq!(pitches.len(), 23);
+        assert_eq!(pitches.first(), pitches.last());
+        assert_eq!(pitches[11], Pitch::new(Note::C, 5));
+        assert_eq!(pitches[12], Pitch::new(Note::A, 4));
+    }
+}
@@ -0,0 +1,363 @@
+use chrono::{Days, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeSet, HashMap},
+    env,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use daily_scale::{scales::parse_scales, tunings::TuningSpec};
+
+/// One practice session, stored as a line of JSON in the log file.
+///
+/// The tuning and scale are stored by their command-line names and the root with sharps, so
+/// entries can be matched against generated scales no matter how they were spelled.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub date: NaiveDate,
+    pub tuning: String,
+    pub root: String,
+    pub scale: String,
+    pub starting_fret: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tempo: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// How well the session went, from 1 (couldn't play it) to 5 (effortless), used for spaced
+    /// repetition.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rating: Option<u8>,
+}
+
+/// `$XDG_DATA_HOME/daily-scale/log.jsonl`, falling back to `~/.local/share`.
+pub fn default_log_path() -> Option<PathBuf> {
+    env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local").join("share")))
+        .map(|dir| dir.join("daily-scale").join("log.jsonl"))
+}
+
+/// The log file given as an option, or the default location.
+pub fn log_path(log_file: Option<&Path>) -> Result<PathBuf, String> {
+    log_file
+        .map(Path::to_path_buf)
+        .or_else(default_log_path)
+        .ok_or_else(|| {
+            String::from("could not find the practice log, set $XDG_DATA_HOME or --log-file")
+        })
+}
+
+/// Reads every entry of the log. A missing log holds no entries yet.
+pub fn read_log(path: &Path) -> Result<Vec<LogEntry>, String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => {
+            parse_log(&contents).map_err(|error| format!("{}: {}", path.display(), error))
+        }
+        Err(_) if !path.exists() => Ok(Vec::new()),
+        Err(error) => Err(format!("{}: {}", path.display(), error)),
+    }
+}
+
+/// Adds an entry at the end of the log, creating the file and its directory if needed.
+pub fn append_entry(path: &Path, entry: &LogEntry) -> Result<(), String> {
+    let error = |error: std::io::Error| format!("{}: {}", path.display(), error);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(error)?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(error)?;
+    let line = serde_json::to_string(entry).map_err(|error| error.to_string())?;
+    writeln!(file, "{}", line).map_err(error)
+}
+
+/// Replaces the whole log with `entries`.
+pub fn write_log(path: &Path, entries: &[LogEntry]) -> Result<(), String> {
+    let mut contents = String::new();
+    for entry in entries {
+        contents.push_str(&serde_json::to_string(entry).map_err(|error| error.to_string())?);
+        contents.push('\n');
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|error| format!("{}: {}", path.display(), error))?;
+    }
+    fs::write(path, contents).map_err(|error| format!("{}: {}", path.display(), error))
+}
+
+fn parse_log(contents: &str) -> Result<Vec<LogEntry>, String> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str::<LogEntry>(line)
+                .map_err(|error| format!("line {}: {}", index + 1, error))
+        })
+        .collect()
+}
+
+/// Summary of the practice log.
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    /// Consecutive days practiced up to today, or up to yesterday if today isn't logged yet.
+    pub current_streak: usize,
+    pub longest_streak: usize,
+    pub total_sessions: usize,
+    /// Sessions per scale, tuning and root, most practiced first, including the candidates never
+    /// practiced.
+    pub scales: Vec<(String, usize)>,
+    pub tunings: Vec<(String, usize)>,
+    pub roots: Vec<(String, usize)>,
+}
+
+/// The scales, roots and tunings the scale of the day is picked from, stored like in
+/// [`LogEntry`], so the ones never practiced count as well.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Candidates {
+    pub scales: Vec<String>,
+    pub roots: Vec<String>,
+    pub tunings: Vec<String>,
+}
+
+pub fn compute_stats(entries: &[LogEntry], candidates: &Candidates, today: NaiveDate) -> Stats {
+    let days: BTreeSet<NaiveDate> = entries.iter().map(|entry| entry.date).collect();
+    let (current_streak, longest_streak) = streaks(&days, today);
+    Stats {
+        current_streak,
+        longest_streak,
+        total_sessions: entries.len(),
+        scales: count_by(entries, &candidates.scales, |entry| &entry.scale),
+        tunings: count_by(entries, &candidates.tunings, |entry| &entry.tuning),
+        roots: count_by(entries, &candidates.roots, |entry| &entry.root),
+    }
+}
+
+/// The current and the longest run of consecutive practice days.
+fn streaks(days: &BTreeSet<NaiveDate>, today: NaiveDate) -> (usize, usize) {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for day in days {
+        run = match previous {
+            Some(previous) if previous.checked_add_days(Days::new(1)) == Some(*day) => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        previous = Some(*day);
+    }
+
+    let mut day = if days.contains(&today) {
+        Some(today)
+    } else {
+        today.checked_sub_days(Days::new(1))
+    };
+    let mut current = 0;
+    while let Some(practiced) = day.filter(|day| days.contains(day)) {
+        current += 1;
+        day = practiced.checked_sub_days(Days::new(1));
+    }
+    (current, longest)
+}
+
+/// Sessions per key, starting every candidate at 0 sessions.
+fn count_by<'a>(
+    entries: &'a [LogEntry],
+    candidates: &'a [String],
+    key: impl Fn(&LogEntry) -> &String,
+) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&String, usize> =
+        candidates.iter().map(|candidate| (candidate, 0)).collect();
+    for entry in entries {
+        *counts.entry(key(entry)).or_default() += 1;
+    }
+    let mut counts: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(name, count)| (name.clone(), count))
+        .collect();
+    counts.sort_by(|(name, count), (other_name, other_count)| {
+        other_count.cmp(count).then_with(|| name.cmp(other_name))
+    });
+    counts
+}
+
+pub fn render_stats(stats: &Stats) -> String {
+    if stats.total_sessions == 0 {
+        return String::from(
+            "No practice sessions logged yet, run `daily-scale done` after practicing\n",
+        );
+    }
+    let mut output = String::new();
+    output.push_str(&format!(
+        "Current streak: {}\n",
+        plural(stats.current_streak, "day")
+    ));
+    output.push_str(&format!(
+        "Longest streak: {}\n",
+        plural(stats.longest_streak, "day")
+    ));
+    output.push_str(&format!("Total sessions: {}\n", stats.total_sessions));
+    push_most_and_least(
+        &mut output,
+        "scale",
+        &stats.scales,
+        |key| match parse_scales(key).as_deref() {
+            Ok([scale]) => scale.to_string(),
+            _ => key.to_string(),
+        },
+    );
+    push_most_and_least(&mut output, "root", &stats.roots, str::to_string);
+    push_most_and_least(&mut output, "tuning", &stats.tunings, |key| {
+        key.parse::<TuningSpec>()
+            .map(|tuning| tuning.to_string())
+            .unwrap_or_else(|_| key.to_string())
+    });
+    output
+}
+
+fn push_most_and_least(
+    output: &mut String,
+    label: &str,
+    counts: &[(String, usize)],
+    name: impl Fn(&str) -> String,
+) {
+    if let Some((most, most_count)) = counts.first() {
+        output.push_str(&format!(
+            "Most practiced {}: {} ({})\n",
+            label,
+            name(most),
+            plural(*most_count, "session")
+        ));
+    }
+    if let [_, .., (least, least_count)] = counts {
+        output.push_str(&format!(
+            "Least practiced {}: {} ({})\n",
+            label,
+            name(least),
+            plural(*least_count, "session")
+        ));
+    }
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("{} {}", count, noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(date: &str, scale: &str, root: &str) -> LogEntry {
+        LogEntry {
+            date: date.parse().unwrap(),
+            tuning: String::from("standard-e6"),
+            root: String::from(root),
+            scale: String::from(scale),
+            starting_fret: 5,
+            tempo: None,
+            notes: None,
+            rating: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_log() {
+        let entries = parse_log(
+            r#"{"date":"2026-10-01","tuning":"standard-e6","root":"A","scale":"dorian","starting_fret":5,"tempo":90}
+
+{"date":"2026-10-02","tuning":"D2,A2,D3,G3,A3,D4","root":"D","scale":"1,2,b3,5,6","starting_fret":0,"notes":"slow"}
+"#,
+        )
+        .unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].tempo, Some(90));
+        assert_eq!(entries[1].notes.as_deref(), Some("slow"));
+        assert!(parse_log("{\"date\":\"2026-10-01\"}")
+            .unwrap_err()
+            .starts_with("line 1:"));
+
+        let line = serde_json::to_string(&entries[0]).unwrap();
+        assert!(!line.contains("notes"));
+        assert_eq!(parse_log(&line).unwrap()[0], entries[0]);
+    }
+
+    #[test]
+    fn test_compute_stats() {
+        let entries = [
+            entry("2026-09-01", "dorian", "A"),
+            entry("2026-09-02", "dorian", "C"),
+            entry("2026-09-03", "major", "A"),
+            entry("2026-10-16", "dorian", "A"),
+            entry("2026-10-16", "locrian", "F#"),
+            entry("2026-10-17", "major", "A"),
+        ];
+        let today = "2026-10-18".parse().unwrap();
+        let stats = compute_stats(&entries, &Candidates::default(), today);
+        assert_eq!(stats.current_streak, 2);
+        assert_eq!(stats.longest_streak, 3);
+        assert_eq!(stats.total_sessions, 6);
+        assert_eq!(
+            stats.scales,
+            vec![
+                (String::from("dorian"), 3),
+                (String::from("major"), 2),
+                (String::from("locrian"), 1),
+            ]
+        );
+        assert_eq!(stats.roots[0], (String::from("A"), 4));
+
+        let later = "2026-10-19".parse().unwrap();
+        assert_eq!(
+            compute_stats(&entries, &Candidates::default(), later).current_streak,
+            0
+        );
+
+        let output = render_stats(&stats);
+        assert!(output.contains("Current streak: 2 days\n"));
+        assert!(output.contains("Most practiced scale: Dorian (3 sessions)\n"));
+        assert!(output.contains("Least practiced scale: Locrian (1 session)\n"));
+        assert!(output.contains("Most practiced tuning: Standard E (6 string) (6 sessions)\n"));
+        assert!(
+            render_stats(&compute_stats(&[], &Candidates::default(), today))
+                .starts_with("No practice sessions")
+        );
+    }
+
+    #[test]
+    fn test_compute_stats_never_practiced() {
+        let entries = [
+            entry("2026-10-16", "dorian", "A"),
+            entry("2026-10-17", "dorian", "A"),
+        ];
+        let candidates = Candidates {
+            scales: vec![String::from("dorian"), String::from("lydian")],
+            roots: vec![String::from("A"), String::from("C"), String::from("F#")],
+            tunings: vec![String::from("standard-e6")],
+        };
+        let today = "2026-10-18".parse().unwrap();
+        let stats = compute_stats(&entries, &candidates, today);
+        assert_eq!(
+            stats.scales,
+            vec![(String::from("dorian"), 2), (String::from("lydian"), 0)]
+        );
+        assert_eq!(stats.roots.len(), 3);
+        assert_eq!(stats.tunings, vec![(String::from("standard-e6"), 2)]);
+
+        let output = render_stats(&stats);
+        assert!(output.contains("Least practiced scale: Lydian (0 sessions)\n"));
+        assert!(output.contains("Least practiced root: F# (0 sessions)\n"));
+        assert!(!output.contains("Least practiced tuning"));
+        // With a single scale logged and nothing else to pick from, there is no least.
+        let output = render_stats(&compute_stats(&entries, &Candidates::default(), today));
+        assert!(output.contains("Most practiced scale: Dorian (2 sessions)\n"));
+        assert!(!output.contains("Least practiced scale"));
+    }
+}
@@ -0,0 +1,211 @@
+//! A memorization quiz on a blank fret board, where only the roots are shown and the other
+//! scale notes have to be named from memory.
+
+use rand::{seq::IndexedRandom, RngCore};
+use std::io::{self, BufRead, Write};
+
+use crate::{
+    format::Format,
+    fret_board::{FretBoard, FretPosition, Highlight},
+    scales::Degree,
+    spelling::SpelledNote,
+};
+
+/// A scale position to name, as the index of its string counted from the lowest string and its
+/// fret.
+pub type Question = (usize, usize);
+
+/// Hides every scale note of the fret board except the roots.
+pub fn hide_scale_notes(fret_board: &mut FretBoard) {
+    for string in &mut fret_board.strings {
+        for position in &mut string.positions {
+            if position.step.is_some_and(|step| step != 0) {
+                position.highlight = Highlight::Hidden;
+            }
+        }
+    }
+}
+
+/// Up to `count` different hidden positions of the fret board, in random order.
+pub fn pick_questions<R: RngCore + ?Sized>(
+    rng: &mut R,
+    fret_board: &FretBoard,
+    count: usize,
+) -> Vec<Question> {
+    let hidden: Vec<Question> = fret_board
+        .scale_positions()
+        .filter(|(_, position)| position.highlight == Highlight::Hidden)
+        .map(|(string_index, position)| (string_index, position.fret))
+        .collect();
+    hidden.choose_multiple(rng, count).copied().collect()
+}
+
+/// Whether `answer` names the note at `position`, either as a note name such as `F#` or `Gb`
+/// or as its interval from the root such as `b3`, with `R` or `1` for the root.
+pub fn check_answer(answer: &str, position: &FretPosition) -> bool {
+    let answer = answer.trim();
+    let Some(step) = position.step else {
+        return false;
+    };
+    if let Ok(note) = answer.parse::<SpelledNote>() {
+        return note.note() == position.note;
+    }
+    if answer.eq_ignore_ascii_case("r") {
+        return step == 0;
+    }
+    answer
+        .parse::<Degree>()
+        .is_ok_and(|degree| degree.get_step() == Some(step))
+}
+
+/// Asks for the note at every question, reading one answer per line from `input`, and returns
+/// the number of right answers. Strings are numbered from the highest, as guitarists do. Running
+/// out of input counts the remaining questions as wrong.
+pub fn ask_questions(
+    fret_board: &FretBoard,
+    questions: &[Question],
+    format: &Format,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<usize> {
+    let num_strings = fret_board.strings.len();
+    let mut score = 0;
+    for (number, (string_index, fret)) in questions.iter().enumerate() {
+        let Some(position) = fret_board.strings[*string_index]
+            .positions
+            .iter()
+            .find(|position| position.fret == *fret)
+        else {
+            continue;
+        };
+        write!(
+            output,
+            "{}/{}. String {}, fret {}: ",
+            number + 1,
+            questions.len(),
+            num_strings - string_index,
+            fret
+        )?;
+        output.flush()?;
+
+        let mut answer = String::new();
+        let answered = input.read_line(&mut answer)? > 0;
+        if !answered {
+            writeln!(output)?;
+        }
+        let solution = format!(
+            "{} ({})",
+            format.spelling.name(position.note),
+            format.degree(position.step.unwrap_or_default())
+        );
+        if answered && check_answer(&answer, position) {
+            score += 1;
+            writeln!(output, "Right, it's {}", solution)?;
+        } else {
+            writeln!(output, "Wrong, it's {}", solution)?;
+        }
+    }
+    Ok(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fret_board::{build_fret_board, FRET_SPAN},
+        notes::Note,
+        random::SplitMix64,
+        scales::Scale,
+        spelling::Spelling,
+        tunings::Tuning,
+    };
+    use std::io::Cursor;
+
+    fn a_minor_pentatonic() -> FretBoard {
+        build_fret_board(
+            &Tuning::StandardE6.get_pitches(),
+            5,
+            FRET_SPAN,
+            &Scale::PentatonicMinor.get_notes(Note::A),
+        )
+    }
+
+    fn position(fret_board: &FretBoard, (string_index, fret): Question) -> &FretPosition {
+        fret_board.strings[string_index]
+            .positions
+            .iter()
+            .find(|position| position.fret == fret)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_pick_questions() {
+        let mut fret_board = a_minor_pentatonic();
+        hide_scale_notes(&mut fret_board);
+        let hidden = fret_board
+            .scale_positions()
+            .filter(|(_, position)| position.highlight == Highlight::Hidden)
+            .count();
+        assert_eq!(hidden, fret_board.scale_positions().count() - 3);
+
+        let questions = pick_questions(&mut SplitMix64::new(7), &fret_board, 5);
+        assert_eq!(questions.len(), 5);
+        for question in &questions {
+            assert_eq!(
+                position(&fret_board, *question).highlight,
+                Highlight::Hidden
+            );
+            assert_eq!(
+                questions.iter().filter(|other| *other == question).count(),
+                1
+            );
+        }
+        assert_eq!(
+            pick_questions(&mut SplitMix64::new(7), &fret_board, 100).len(),
+            hidden
+        );
+    }
+
+    #[test]
+    fn test_check_answer() {
+        let fret_board = a_minor_pentatonic();
+        // The C on the low E string, a minor third above the root.
+        let c = position(&fret_board, (0, 8));
+        for answer in ["C", "c", "B#", " b3\n"] {
+            assert!(check_answer(answer, c), "{}", answer);
+        }
+        for answer in ["Db", "3", "R", "", "C3"] {
+            assert!(!check_answer(answer, c), "{}", answer);
+        }
+        let root = position(&fret_board, (0, 5));
+        assert!(check_answer("r", root));
+        assert!(check_answer("1", root));
+    }
+
+    #[test]
+    fn test_ask_questions() {
+        let fret_board = a_minor_pentatonic();
+        let format = Format {
+            spelling: Spelling::Sharps,
+            degrees: Scale::PentatonicMinor.get_degrees(),
+            ..Default::default()
+        };
+        let questions = [(0, 8), (3, 7), (5, 5)];
+        let mut output = Vec::new();
+        let score = ask_questions(
+            &fret_board,
+            &questions,
+            &format,
+            Cursor::new("b3\nB\n"),
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(score, 1);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "1/3. String 6, fret 8: Right, it's C (b3)\n\
+             2/3. String 3, fret 7: Wrong, it's D (4)\n\
+             3/3. String 1, fret 5: \nWrong, it's A (1)\n"
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! Randomness that stays the same across versions.
+//!
+//! The scale of the day has to be the same for everyone on a given date, no matter which
+//! version of `daily-scale` or of `rand` they run. [`SplitMix64`] is a pinned generator, and
+//! [`pick`] and [`pick_weighted`] select by rendezvous hashing: every candidate gets a score
+//! from the random draw and its own key, and the best score wins. The pick therefore doesn't
+//! depend on the order of the candidates, and adding or removing one only changes the pick if
+//! it is the one that wins or won.
+
+use rand::RngCore;
+
+/// The SplitMix64 generator by Sebastiano Vigna, fully determined by its seed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> SplitMix64 {
+        SplitMix64 { state: seed }
+    }
+}
+
+impl RngCore for SplitMix64 {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        mix(self.state)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+}
+
+/// The output function of SplitMix64, which scrambles every bit of `value` into every other.
+fn mix(value: u64) -> u64 {
+    let value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    let value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    value ^ (value >> 31)
+}
+
+/// 64-bit FNV-1a hash of `key`, which unlike the hasher of the standard library is fixed.
+pub fn stable_hash(key: &str) -> u64 {
+    key.bytes().fold(0xCBF2_9CE4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01B3)
+    })
+}
+
+/// The score of the candidate with `key` for the random `draw`.
+fn score(draw: u64, key: &str) -> u64 {
+    mix(draw ^ stable_hash(key))
+}
+
+/// Picks one of the `candidates`, each equally likely, identified by `key`. Uses one draw of
+/// `rng`.
+pub fn pick<'a, R: RngCore + ?Sized, T>(
+    rng: &mut R,
+    candidates: &'a [T],
+    key: impl Fn(&T) -> String,
+) -> Option<&'a T> {
+    let draw = rng.next_u64();
+    candidates
+        .iter()
+        .max_by_key(|candidate| score(draw, &key(candidate)))
+}
+
+/// Picks one of the `candidates`, identified by `key`, with a chance proportional to its
+/// `weight`. Candidates without a positive weight are never picked. Uses one draw of `rng`.
+pub fn pick_weighted<'a, R: RngCore + ?Sized, T>(
+    rng: &mut R,
+    candidates: &'a [T],
+    key: impl Fn(&T) -> String,
+    weight: impl Fn(&T) -> f64,
+) -> Option<&'a T> {
+    let draw = rng.next_u64();
+    // The candidate with the largest `u^(1/weight)` for a uniform `u` in (0, 1] wins with a
+    // chance proportional to its weight; comparing the logarithms gives the same order.
+    candidates
+        .iter()
+        .filter(|candidate| weight(candidate) > 0.0)
+        .map(|candidate| {
+            let uniform = ((score(draw, &key(candidate)) >> 11) + 1) as f64 / (1u64 << 53) as f64;
+            (candidate, uniform.ln() / weight(candidate))
+        })
+        .max_by(|(_, score), (_, other)| score.total_cmp(other))
+        .map(|(candidate, _)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_mix_64() {
+        // Reference output of Vigna's splitmix64.c.
+        let mut rng = SplitMix64::new(1_234_567);
+        let output: Vec<u64> = (0..5).map(|_| rng.next_u64()).collect();
+        assert_eq!(
+            output,
+            vec![
+                6457827717110365317,
+                3203168211198807973,
+                9817491932198370423,
+                4593380528125082431,
+                16408922859458223821,
+            ]
+        );
+        assert_eq!(stable_hash(""), 0xCBF2_9CE4_8422_2325);
+        assert_eq!(stable_hash("a"), 0xAF63_DC4C_8601_EC8C);
+    }
+
+    #[test]
+    fn test_pick() {
+        let names = ["dorian", "lydian", "locrian", "major", "phrygian"];
+        let key = |name: &&str| name.to_string();
+        for seed in 0..20 {
+            let picked = pick(&mut SplitMix64::new(seed), &names, key).unwrap();
+            let mut reversed = names;
+            reversed.reverse();
+            assert_eq!(
+                pick(&mut SplitMix64::new(seed), &reversed, key),
+                Some(picked)
+            );
+            let others: Vec<&str> = names
+                .into_iter()
+                .filter(|name| name == picked || *name != "lydian")
+                .collect();
+            assert_eq!(pick(&mut SplitMix64::new(seed), &others, key), Some(picked));
+        }
+        assert_eq!(pick(&mut SplitMix64::new(0), &[] as &[&str], key), None);
+    }
+
+    #[test]
+    fn test_pick_weighted() {
+        let names = ["never", "rare", "often"];
+        let weight = |name: &&str| match *name {
+            "never" => 0.0,
+            "rare" => 1.0,
+            _ => 9.0,
+        };
+        let mut rng = SplitMix64::new(0);
+        let picks: Vec<&str> = (0..1000)
+            .map(|_| *pick_weighted(&mut rng, &names, |name| name.to_string(), weight).unwrap())
+            .collect();
+        let count = |name: &str| picks.iter().filter(|pick| **pick == name).count();
+        assert_eq!(count("never"), 0);
+        assert!((850..950).contains(&count("often")));
+    }
+}
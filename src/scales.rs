@@ -1,7 +1,11 @@
 use clap::ValueEnum;
+use std::{fmt, str::FromStr};
 use strum::{Display, EnumIter};
 
-#[derive(Copy, Clone, Debug, ValueEnum, EnumIter, Display)]
+use crate::notes::{Note, NUM_NOTES};
+
+/// The built-in scales, each defined by its semitone steps from the root.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum, EnumIter, Display)]
 pub enum Scale {
     #[strum(to_string = "Major")]
     Major,
@@ -40,6 +44,7 @@ pub enum Scale {
 }
 
 impl Scale {
+    /// Semitone steps from the root for every note in the scale, in ascending order.
     pub fn get_steps(&self) -> &'static [usize] {
         match self {
             Scale::Major => &[0, 2, 4, 5, 7, 9, 11],
@@ -61,6 +66,353 @@ impl Scale {
             Scale::Locrian => &[0, 1, 3, 5, 6, 8, 10],
         }
     }
+
+    /// The notes of the scale built on `root`, paired with their semitone step from the root.
+    pub fn get_notes(&self, root: Note) -> Vec<(Note, usize)> {
+        self.get_steps()
+            .iter()
+            .map(|step| (root.transpose(*step), *step))
+            .collect()
+    }
+
+    /// The degree of every note in the scale, e.g. `1 2 b3 4 5 b6 b7` for the natural minor.
+    pub fn get_degrees(&self) -> Vec<Degree> {
+        degrees_from_steps(self.get_steps())
+    }
+}
+
+/// Names the steps of a scale: seven-note scales use every degree once, other scales fall
+/// back to [`Degree::from_step`].
+pub fn degrees_from_steps(steps: &[usize]) -> Vec<Degree> {
+    if steps.len() == MAJOR_STEPS.len() {
+        steps
+            .iter()
+            .zip(MAJOR_STEPS)
+            .enumerate()
+            .map(|(index, (step, major_step))| Degree {
+                number: index + 1,
+                alteration: *step as i32 - major_step as i32,
+            })
+            .collect()
+    } else {
+        steps.iter().map(|step| Degree::from_step(*step)).collect()
+    }
+}
+
+impl FromStr for Scale {
+    type Err = String;
+
+    /// Looks up a scale by its command-line name, e.g. `harmonic-minor`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        <Scale as ValueEnum>::from_str(s, true).map_err(|_| format!("Unknown scale: {}", s))
+    }
+}
+
+/// Semitone steps of the major scale, used as the reference for scale degrees.
+pub const MAJOR_STEPS: [usize; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+/// A scale degree such as `5`, `b3` or `#4`, relative to the major scale.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Degree {
+    /// Degree number from 1 to 7.
+    pub number: usize,
+    /// Half steps added to the major scale degree, negative for flats.
+    pub alteration: i32,
+}
+
+impl Degree {
+    /// Semitone step of the degree from the root, if it falls inside the octave.
+    pub fn get_step(&self) -> Option<usize> {
+        let step = MAJOR_STEPS[self.number - 1] as i32 + self.alteration;
+        (0..NUM_NOTES as i32)
+            .contains(&step)
+            .then_some(step as usize)
+    }
+
+    /// The usual degree name of a semitone step when nothing else is known about the scale.
+    pub fn from_step(step: usize) -> Degree {
+        let (number, alteration) = match step % NUM_NOTES {
+            0 => (1, 0),
+            1 => (2, -1),
+            2 => (2, 0),
+            3 => (3, -1),
+            4 => (3, 0),
+            5 => (4, 0),
+            6 => (5, -1),
+            7 => (5, 0),
+            8 => (6, -1),
+            9 => (6, 0),
+            10 => (7, -1),
+            _ => (7, 0),
+        };
+        Degree { number, alteration }
+    }
+}
+
+impl fmt::Display for Degree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let accidental = if self.alteration < 0 { "b" } else { "#" };
+        write!(
+            f,
+            "{}{}",
+            accidental.repeat(self.alteration.unsigned_abs() as usize),
+            self.number
+        )
+    }
+}
+
+impl FromStr for Degree {
+    type Err = String;
+
+    /// Parses a degree such as `5`, `b3` or `#4`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let number_start = s.find(|c: char| !matches!(c, 'b' | '#')).unwrap_or(s.len());
+        let (accidentals, number) = s.split_at(number_start);
+        let number = number
+            .parse::<usize>()
+            .ok()
+            .filter(|number| (1..=MAJOR_STEPS.len()).contains(number))
+            .ok_or_else(|| format!("Invalid scale degree: '{}'", s))?;
+        let alteration = accidentals
+            .chars()
+            .map(|accidental| if accidental == '#' { 1 } else { -1 })
+            .sum();
+        Ok(Degree { number, alteration })
+    }
+}
+
+/// A scale defined on the command line, either by semitone steps or by a degree formula.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CustomScale {
+    steps: Vec<usize>,
+    degrees: Option<Vec<Degree>>,
+}
+
+impl CustomScale {
+    /// Builds a scale from semitone steps such as `[0, 2, 3, 6, 7, 8, 11]`.
+    pub fn from_steps(steps: &[usize]) -> Result<CustomScale, String> {
+        Ok(CustomScale {
+            steps: validate_steps(steps.to_vec())?,
+            degrees: None,
+        })
+    }
+
+    /// Builds a scale from degrees such as `1 2 b3 #4 5 b6 7`.
+    pub fn from_degrees(degrees: &[Degree]) -> Result<CustomScale, String> {
+        let mut degrees = degrees.to_vec();
+        let steps = degrees
+            .iter()
+            .map(|degree| {
+                degree
+                    .get_step()
+                    .ok_or_else(|| format!("Scale degree {} is out of range", degree))
+            })
+            .collect::<Result<Vec<usize>, String>>()?;
+        degrees.sort_by_key(|degree| degree.get_step());
+        if let Some(pair) = degrees
+            .windows(2)
+            .find(|pair| pair[0].get_step() == pair[1].get_step())
+        {
+            return Err(format!(
+                "Scale degrees {} and {} are the same note",
+                pair[0], pair[1]
+            ));
+        }
+        Ok(CustomScale {
+            steps: validate_steps(steps)?,
+            degrees: Some(degrees),
+        })
+    }
+
+    pub fn get_steps(&self) -> &[usize] {
+        &self.steps
+    }
+
+    /// The degrees the scale was defined with, if it was given as a degree formula.
+    pub fn get_degrees(&self) -> Option<&[Degree]> {
+        self.degrees.as_deref()
+    }
+}
+
+fn validate_steps(mut steps: Vec<usize>) -> Result<Vec<usize>, String> {
+    if let Some(step) = steps.iter().find(|step| **step >= NUM_NOTES) {
+        return Err(format!(
+            "Scale step {} is out of range, steps must be below {}",
+            step, NUM_NOTES
+        ));
+    }
+    steps.sort();
+    if let Some(pair) = steps.windows(2).find(|pair| pair[0] == pair[1]) {
+        return Err(format!("Scale step {} appears more than once", pair[0]));
+    }
+    if steps.first() != Some(&0) {
+        return Err(String::from("A scale must contain its root"));
+    }
+    Ok(steps)
+}
+
+impl fmt::Display for CustomScale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let formula = match self.degrees {
+            Some(ref degrees) => degrees
+                .iter()
+                .map(|degree| degree.to_string())
+                .collect::<Vec<String>>(),
+            None => self.steps.iter().map(|step| step.to_string()).collect(),
+        };
+        write!(f, "Custom ({})", formula.join(" "))
+    }
+}
+
+/// Either one of the built-in scales or a user-defined one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScaleSpec {
+    Preset(Scale),
+    Custom(CustomScale),
+}
+
+impl ScaleSpec {
+    /// Semitone steps from the root for every note in the scale, in ascending order.
+    pub fn get_steps(&self) -> &[usize] {
+        match self {
+            ScaleSpec::Preset(scale) => scale.get_steps(),
+            ScaleSpec::Custom(scale) => scale.get_steps(),
+        }
+    }
+
+    /// The notes of the scale built on `root`, paired with their semitone step from the root.
+    pub fn get_notes(&self, root: Note) -> Vec<(Note, usize)> {
+        self.get_steps()
+            .iter()
+            .map(|step| (root.transpose(*step), *step))
+            .collect()
+    }
+
+    /// The degree of every note in the scale, using the formula of custom scales if given.
+    pub fn get_degrees(&self) -> Vec<Degree> {
+        match self {
+            ScaleSpec::Custom(scale) => scale
+                .get_degrees()
+                .map(<[Degree]>::to_vec)
+                .unwrap_or_else(|| degrees_from_steps(scale.get_steps())),
+            ScaleSpec::Preset(scale) => scale.get_degrees(),
+        }
+    }
+
+    /// Command-line name of the scale, which [`parse_scales`] reads back into the same scale.
+    pub fn key(&self) -> String {
+        match self {
+            ScaleSpec::Preset(scale) => scale
+                .to_possible_value()
+                .map(|value| value.get_name().to_string())
+                .unwrap_or_default(),
+            ScaleSpec::Custom(scale) => match scale.get_degrees() {
+                Some(degrees) => degrees
+                    .iter()
+                    .map(|degree| degree.to_string())
+                    .collect::<Vec<String>>()
+                    .join(","),
+                None => scale
+                    .get_steps()
+                    .iter()
+                    .map(|step| step.to_string())
+                    .collect::<Vec<String>>()
+                    .join(","),
+            },
+        }
+    }
+}
+
+impl From<Scale> for ScaleSpec {
+    fn from(scale: Scale) -> Self {
+        ScaleSpec::Preset(scale)
+    }
+}
+
+impl fmt::Display for ScaleSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaleSpec::Preset(scale) => write!(f, "{}", scale),
+            ScaleSpec::Custom(scale) => write!(f, "{}", scale),
+        }
+    }
+}
+
+/// Parses a comma separated list of scales.
+///
+/// Each entry is either a scale name such as `dorian` or a custom scale. Custom scales are
+/// written in ascending order as semitone steps starting from the root (`0,2,3,6,7,8,11`) or
+/// as a degree formula starting from the root (`1,2,b3,#4,5,b6,7`). A new custom scale begins
+/// at every root, so `dorian,0,2,3,6,7,8,11,1,2,b3,5,6` holds three scales.
+pub fn parse_scales(s: &str) -> Result<Vec<ScaleSpec>, String> {
+    let mut scales = Vec::new();
+    let mut custom: Option<(bool, Vec<&str>)> = None;
+    for token in s.split(',').map(str::trim) {
+        if let Ok(scale) = token.parse::<Scale>() {
+            if let Some((by_steps, tokens)) = custom.take() {
+                scales.push(parse_custom_scale(by_steps, &tokens)?);
+            }
+            scales.push(ScaleSpec::Preset(scale));
+            continue;
+        }
+        let starts_scale_by_steps = match (token, &custom) {
+            ("0", _) => Some(true),
+            ("1", Some((true, tokens)))
+                if tokens
+                    .last()
+                    .and_then(|step| step.parse::<usize>().ok())
+                    .is_none_or(|step| step < 1) =>
+            {
+                None
+            }
+            ("1", _) => Some(false),
+            _ => None,
+        };
+        match (custom.as_mut(), starts_scale_by_steps) {
+            (_, Some(by_steps)) => {
+                if let Some((by_steps, tokens)) = custom.take() {
+                    scales.push(parse_custom_scale(by_steps, &tokens)?);
+                }
+                custom = Some((by_steps, vec![token]));
+            }
+            (Some((_, tokens)), None) => tokens.push(token),
+            (None, None) => {
+                return Err(format!(
+                    "Unknown scale: '{}' (custom scales must start with their root, 0 or 1)",
+                    token
+                ))
+            }
+        }
+    }
+    if let Some((by_steps, tokens)) = custom {
+        scales.push(parse_custom_scale(by_steps, &tokens)?);
+    }
+    Ok(scales)
+}
+
+fn parse_custom_scale(by_steps: bool, tokens: &[&str]) -> Result<ScaleSpec, String> {
+    let formula = tokens.join(",");
+    let scale = if by_steps {
+        let steps = tokens
+            .iter()
+            .map(|token| {
+                token
+                    .parse::<usize>()
+                    .map_err(|_| format!("Invalid scale step: '{}'", token))
+            })
+            .collect::<Result<Vec<usize>, String>>();
+        steps.and_then(|steps| CustomScale::from_steps(&steps))
+    } else {
+        let degrees = tokens
+            .iter()
+            .map(|token| token.parse::<Degree>())
+            .collect::<Result<Vec<Degree>, String>>();
+        degrees.and_then(|degrees| CustomScale::from_degrees(&degrees))
+    };
+    scale
+        .map(ScaleSpec::Custom)
+        .map_err(|error| format!("{} in scale '{}'", error, formula))
 }
 
 #[cfg(test)]
@@ -72,4 +424,84 @@ mod tests {
         assert_eq!(Scale::HarmonicMinor.get_steps(), &[0, 2, 3, 5, 7, 8, 11]);
         assert_eq!(Scale::Phrygian.get_steps(), &[0, 1, 3, 5, 7, 8, 10]);
     }
+
+    #[test]
+    fn test_get_notes() {
+        assert_eq!(
+            Scale::PentatonicMinor.get_notes(Note::E),
+            vec![
+                (Note::E, 0),
+                (Note::G, 3),
+                (Note::A, 5),
+                (Note::B, 7),
+                (Note::D, 10)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_degree() {
+        assert_eq!("b3".parse::<Degree>().unwrap().get_step(), Some(3));
+        assert_eq!("#4".parse::<Degree>().unwrap().get_step(), Some(6));
+        assert_eq!("b1".parse::<Degree>().unwrap().get_step(), None);
+        assert!("8".parse::<Degree>().is_err());
+        assert!("3b".parse::<Degree>().is_err());
+        assert_eq!(Degree::from_step(10).to_string(), "b7");
+    }
+
+    #[test]
+    fn test_get_degrees() {
+        let degrees = |scale: Scale| {
+            scale
+                .get_degrees()
+                .iter()
+                .map(|degree| degree.to_string())
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+        assert_eq!(degrees(Scale::Lydian), "1 2 3 #4 5 6 7");
+        assert_eq!(degrees(Scale::PentatonicMinor), "1 b3 4 5 b7");
+    }
+
+    #[test]
+    fn test_parse_scales() {
+        let scales = parse_scales("dorian,0,2,3,6,7,8,11,1,2,b3,#4,5,b6,7,locrian").unwrap();
+        assert_eq!(scales.len(), 4);
+        assert_eq!(scales[0], ScaleSpec::Preset(Scale::Dorian));
+        assert_eq!(scales[1].get_steps(), &[0, 2, 3, 6, 7, 8, 11]);
+        assert_eq!(scales[2].get_steps(), &[0, 2, 3, 6, 7, 8, 11]);
+        assert_eq!(scales[2].to_string(), "Custom (1 2 b3 #4 5 b6 7)");
+        assert_eq!(scales[3], ScaleSpec::Preset(Scale::Locrian));
+        assert_eq!(
+            parse_scales("0,1,3,0,2,4").unwrap(),
+            vec![
+                ScaleSpec::Custom(CustomScale::from_steps(&[0, 1, 3]).unwrap()),
+                ScaleSpec::Custom(CustomScale::from_steps(&[0, 2, 4]).unwrap()),
+            ]
+        );
+        for scale in scales {
+            assert_eq!(parse_scales(&scale.key()).unwrap(), vec![scale]);
+        }
+        assert_eq!(
+            ScaleSpec::Preset(Scale::PentatonicMinor).key(),
+            "pentatonic-minor"
+        );
+    }
+
+    #[test]
+    fn test_parse_scales_errors() {
+        assert!(parse_scales("dorain").is_err());
+        assert!(parse_scales("2,3,5").is_err());
+        assert!(parse_scales("0,2,2,5").is_err());
+        assert!(parse_scales("0,2,12").is_err());
+        assert!(parse_scales("1,b3,#2,5").is_err());
+        assert!(parse_scales("1,2,3,#7").is_err());
+        assert!(parse_scales("0,2,b3").is_err());
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert!(matches!("dorian".parse::<Scale>(), Ok(Scale::Dorian)));
+        assert!("dorain".parse::<Scale>().is_err());
+    }
 }
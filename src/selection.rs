@@ -0,0 +1,394 @@
+use chrono::{Days, NaiveDate};
+use clap::ValueEnum;
+use rand::RngCore;
+use std::collections::HashMap;
+
+use daily_scale::{
+    notes::Note,
+    random::{pick, pick_weighted},
+    scales::ScaleSpec,
+};
+
+use crate::practice_log::LogEntry;
+
+/// How the root and scale of the day are picked: every combination equally likely, weighted
+/// by the practice log with [`History`], or due for review in a spaced repetition [`Schedule`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Selection {
+    #[default]
+    Random,
+    History,
+    Spaced,
+}
+
+/// Picks the root and scale of the day from the practice log.
+pub enum Selector {
+    History(History),
+    Schedule(Schedule),
+}
+
+impl Selector {
+    /// Picks one of the `candidates`, whose root and scale are given by `combination`.
+    pub fn choose<'a, R: RngCore + ?Sized, T>(
+        &self,
+        rng: &mut R,
+        candidates: &'a [T],
+        combination: impl Fn(&T) -> (Note, &ScaleSpec),
+    ) -> &'a T {
+        match self {
+            Selector::History(history) => history.choose(rng, candidates, combination),
+            Selector::Schedule(schedule) => schedule.choose(rng, candidates, combination),
+        }
+    }
+}
+
+/// When each combination of root and scale was last practiced.
+pub struct History {
+    last_practiced: HashMap<(String, String), NaiveDate>,
+    today: NaiveDate,
+}
+
+impl History {
+    /// Collects the sessions before `today`. Sessions logged today are left out so the scale of
+    /// the day doesn't change after running `done`.
+    pub fn new(entries: &[LogEntry], today: NaiveDate) -> History {
+        let mut last_practiced: HashMap<(String, String), NaiveDate> = HashMap::new();
+        for entry in entries.iter().filter(|entry| entry.date < today) {
+            let date = last_practiced
+                .entry((entry.root.clone(), entry.scale.clone()))
+                .or_insert(entry.date);
+            *date = (*date).max(entry.date);
+        }
+        History {
+            last_practiced,
+            today,
+        }
+    }
+
+    /// Days since the combination was last practiced, or `None` if it never was.
+    fn days_since(&self, root: Note, scale: &ScaleSpec) -> Option<i64> {
+        self.last_practiced
+            .get(&(String::from(root.to_str(false)), scale.key()))
+            .map(|date| (self.today - *date).num_days())
+    }
+
+    /// Picks one of the `candidates`, whose root and scale are given by `combination`.
+    ///
+    /// While some combinations were never practiced, one of them is picked. Once all of them
+    /// were, the chance of a combination grows with the number of days since it was practiced.
+    pub fn choose<'a, R: RngCore + ?Sized, T>(
+        &self,
+        rng: &mut R,
+        candidates: &'a [T],
+        combination: impl Fn(&T) -> (Note, &ScaleSpec),
+    ) -> &'a T {
+        let (keys, days): (Vec<String>, Vec<Option<i64>>) = candidates
+            .iter()
+            .map(|candidate| {
+                let (root, scale) = combination(candidate);
+                (combination_key(root, scale), self.days_since(root, scale))
+            })
+            .unzip();
+        let indices: Vec<usize> = (0..candidates.len()).collect();
+        let key = |index: &usize| keys[*index].clone();
+        let index = if days.contains(&None) {
+            let unpracticed: Vec<usize> = indices
+                .into_iter()
+                .filter(|index| days[*index].is_none())
+                .collect();
+            *pick(rng, &unpracticed, key).unwrap()
+        } else {
+            *pick_weighted(rng, &indices, key, |index| {
+                days[*index].unwrap_or_default().max(1) as f64
+            })
+            .unwrap()
+        };
+        &candidates[index]
+    }
+}
+
+/// Identifies a combination of root and scale when picking one.
+fn combination_key(root: Note, scale: &ScaleSpec) -> String {
+    format!("{} {}", root.to_str(false), scale.key())
+}
+
+const INITIAL_EASINESS: f64 = 2.5;
+const MIN_EASINESS: f64 = 1.3;
+/// Lowest grade that counts as remembered; anything below starts the repetitions over.
+const PASSING_GRADE: u8 = 3;
+
+/// Spaced repetition state of one combination, following the SM-2 algorithm.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Review {
+    pub easiness: f64,
+    /// Number of passing grades in a row.
+    pub repetitions: u32,
+    /// Days until the next review.
+    pub interval: u64,
+    pub due: NaiveDate,
+}
+
+impl Review {
+    /// The state after practicing on `date` with `grade`, from 1 (couldn't play it) to 5
+    /// (effortless), given the state before if the combination was rated already.
+    pub fn next(previous: Option<&Review>, grade: u8, date: NaiveDate) -> Review {
+        let (easiness, repetitions, interval) = previous
+            .map(|review| (review.easiness, review.repetitions, review.interval))
+            .unwrap_or((INITIAL_EASINESS, 0, 0));
+        let (repetitions, interval) = if grade < PASSING_GRADE {
+            (0, 1)
+        } else {
+            let interval = match repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (interval as f64 * easiness).round() as u64,
+            };
+            (repetitions + 1, interval)
+        };
+        let distance = (5 - grade.min(5)) as f64;
+        let easiness = (easiness + 0.1 - distance * (0.08 + distance * 0.02)).max(MIN_EASINESS);
+        Review {
+            easiness,
+            repetitions,
+            interval,
+            due: date
+                .checked_add_days(Days::new(interval))
+                .unwrap_or(NaiveDate::MAX),
+        }
+    }
+}
+
+/// When each combination of root and scale in one tuning is due for review.
+pub struct Schedule {
+    reviews: HashMap<(String, String), Review>,
+    today: NaiveDate,
+}
+
+impl Schedule {
+    /// Replays the ratings given in `tuning` before `today`, oldest first. Ratings given today
+    /// are left out so the scale of the day doesn't change after running `rate`.
+    pub fn new(entries: &[LogEntry], tuning: &str, today: NaiveDate) -> Schedule {
+        let mut rated: Vec<&LogEntry> = entries
+            .iter()
+            .filter(|entry| entry.tuning == tuning && entry.date < today)
+            .filter(|entry| entry.rating.is_some())
+            .collect();
+        rated.sort_by_key(|entry| entry.date);
+        let mut reviews: HashMap<(String, String), Review> = HashMap::new();
+        for entry in rated {
+            let key = (entry.root.clone(), entry.scale.clone());
+            let review = Review::next(reviews.get(&key), entry.rating.unwrap(), entry.date);
+            reviews.insert(key, review);
+        }
+        Schedule { reviews, today }
+    }
+
+    pub fn review(&self, root: Note, scale: &ScaleSpec) -> Option<&Review> {
+        self.reviews
+            .get(&(String::from(root.to_str(false)), scale.key()))
+    }
+
+    /// Picks one of the `candidates`, whose root and scale are given by `combination`.
+    ///
+    /// Combinations that are due are picked first, the longer overdue the more likely. Without
+    /// any, a combination that was never rated is picked, and once everything was rated the one
+    /// that is due next.
+    pub fn choose<'a, R: RngCore + ?Sized, T>(
+        &self,
+        rng: &mut R,
+        candidates: &'a [T],
+        combination: impl Fn(&T) -> (Note, &ScaleSpec),
+    ) -> &'a T {
+        let (keys, due): (Vec<String>, Vec<Option<NaiveDate>>) = candidates
+            .iter()
+            .map(|candidate| {
+                let (root, scale) = combination(candidate);
+                (
+                    combination_key(root, scale),
+                    self.review(root, scale).map(|review| review.due),
+                )
+            })
+            .unzip();
+        let indices: Vec<usize> = (0..candidates.len()).collect();
+        let key = |index: &usize| keys[*index].clone();
+        let overdue: Vec<(usize, i64)> = indices
+            .iter()
+            .filter_map(|index| due[*index].map(|date| (*index, (self.today - date).num_days())))
+            .filter(|(_, days)| *days >= 0)
+            .collect();
+        let index = if !overdue.is_empty() {
+            pick_weighted(
+                rng,
+                &overdue,
+                |(index, _)| key(index),
+                |(_, days)| (days + 1) as f64,
+            )
+            .unwrap()
+            .0
+        } else if due.contains(&None) {
+            let unrated: Vec<usize> = indices
+                .into_iter()
+                .filter(|index| due[*index].is_none())
+                .collect();
+            *pick(rng, &unrated, key).unwrap()
+        } else {
+            let next = due.iter().min().copied().flatten();
+            let upcoming: Vec<usize> = indices
+                .into_iter()
+                .filter(|index| due[*index] == next)
+                .collect();
+            *pick(rng, &upcoming, key).unwrap()
+        };
+        &candidates[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use daily_scale::random::SplitMix64;
+    use daily_scale::scales::Scale;
+
+    fn entry(date: &str, root: &str, scale: &str) -> LogEntry {
+        LogEntry {
+            date: date.parse().unwrap(),
+            tuning: String::from("standard-e6"),
+            root: String::from(root),
+            scale: String::from(scale),
+            starting_fret: 0,
+            tempo: None,
+            notes: None,
+            rating: None,
+        }
+    }
+
+    fn rated(date: &str, root: &str, scale: &str, rating: u8) -> LogEntry {
+        LogEntry {
+            rating: Some(rating),
+            ..entry(date, root, scale)
+        }
+    }
+
+    #[test]
+    fn test_choose() {
+        let today = "2026-10-18".parse().unwrap();
+        let dorian = ScaleSpec::from(Scale::Dorian);
+        let major = ScaleSpec::from(Scale::Major);
+        let candidates = [
+            (Note::A, &dorian),
+            (Note::A, &major),
+            (Note::C, &dorian),
+            (Note::C, &major),
+        ];
+        let choose = |history: &History, rng: &mut SplitMix64| {
+            let pick = history.choose(rng, &candidates, |candidate| *candidate);
+            candidates
+                .iter()
+                .position(|candidate| candidate == pick)
+                .unwrap()
+        };
+        let mut entries = vec![
+            entry("2026-10-01", "A", "dorian"),
+            entry("2026-10-17", "A", "major"),
+            entry("2026-10-16", "C", "dorian"),
+            // Practiced today, which doesn't count yet.
+            entry("2026-10-18", "C", "major"),
+        ];
+
+        let history = History::new(&entries, today);
+        let mut rng = SplitMix64::new(0);
+        for _ in 0..20 {
+            assert_eq!(choose(&history, &mut rng), 3);
+        }
+
+        entries[3].date = "2026-10-17".parse().unwrap();
+        let history = History::new(&entries, today);
+        assert_eq!(history.days_since(Note::A, &dorian), Some(17));
+        let picks: Vec<usize> = (0..1000).map(|_| choose(&history, &mut rng)).collect();
+        let count = |pick: usize| picks.iter().filter(|p| **p == pick).count();
+        assert!(count(0) > count(2));
+        assert!(count(2) > count(1));
+    }
+
+    #[test]
+    fn test_review() {
+        let date: NaiveDate = "2026-10-01".parse().unwrap();
+        let first = Review::next(None, 4, date);
+        assert_eq!((first.repetitions, first.interval), (1, 1));
+        assert_eq!(first.easiness, 2.5);
+        let second = Review::next(Some(&first), 5, date);
+        assert_eq!((second.repetitions, second.interval), (2, 6));
+        assert!((second.easiness - 2.6).abs() < 1e-9);
+        let third = Review::next(Some(&second), 3, date);
+        assert_eq!(third.interval, 16);
+        assert_eq!(third.due, "2026-10-17".parse().unwrap());
+        assert!((third.easiness - 2.46).abs() < 1e-9);
+        let failed = Review::next(Some(&third), 1, date);
+        assert_eq!((failed.repetitions, failed.interval), (0, 1));
+        assert!((failed.easiness - 1.92).abs() < 1e-9);
+        let mut review = failed;
+        for _ in 0..10 {
+            review = Review::next(Some(&review), 1, date);
+        }
+        assert_eq!(review.easiness, MIN_EASINESS);
+    }
+
+    #[test]
+    fn test_schedule() {
+        let today = "2026-10-18".parse().unwrap();
+        let dorian = ScaleSpec::from(Scale::Dorian);
+        let major = ScaleSpec::from(Scale::Major);
+        let candidates = [(Note::A, &dorian), (Note::A, &major), (Note::C, &dorian)];
+        let choose = |schedule: &Schedule, rng: &mut SplitMix64| {
+            let pick = schedule.choose(rng, &candidates, |candidate| *candidate);
+            candidates
+                .iter()
+                .position(|candidate| candidate == pick)
+                .unwrap()
+        };
+        let mut entries = vec![
+            // Due on 2026-10-11, a week overdue.
+            rated("2026-10-01", "A", "dorian", 5),
+            rated("2026-10-05", "A", "dorian", 5),
+            // Due on 2026-10-22.
+            rated("2026-10-10", "A", "major", 4),
+            rated("2026-10-16", "A", "major", 4),
+            // Practiced without a rating, or in another tuning.
+            entry("2026-10-10", "C", "dorian"),
+            LogEntry {
+                tuning: String::from("drop-d6"),
+                ..rated("2026-10-01", "C", "dorian", 5)
+            },
+        ];
+        let mut rng = SplitMix64::new(0);
+
+        let schedule = Schedule::new(&entries, "standard-e6", today);
+        assert_eq!(schedule.review(Note::C, &dorian), None);
+        assert_eq!(choose(&schedule, &mut rng), 0);
+
+        // Reviewed today, which only counts from tomorrow on.
+        entries.push(rated("2026-10-18", "A", "dorian", 5));
+        let schedule = Schedule::new(&entries, "standard-e6", today);
+        assert_eq!(choose(&schedule, &mut rng), 0);
+
+        entries[1].date = "2026-10-17".parse().unwrap();
+        let schedule = Schedule::new(&entries, "standard-e6", today);
+        assert_eq!(choose(&schedule, &mut rng), 2);
+
+        entries.push(rated("2026-10-16", "C", "dorian", 2));
+        let schedule = Schedule::new(&entries, "standard-e6", today);
+        assert_eq!(choose(&schedule, &mut rng), 2);
+
+        // Nothing is due and everything was rated, A major is due first.
+        entries.last_mut().unwrap().rating = Some(4);
+        entries.push(rated("2026-10-17", "C", "dorian", 4));
+        let schedule = Schedule::new(&entries, "standard-e6", today);
+        assert_eq!(
+            schedule.review(Note::C, &dorian).unwrap().due,
+            "2026-10-23".parse().unwrap()
+        );
+        for _ in 0..20 {
+            assert_eq!(choose(&schedule, &mut rng), 1);
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use std::{fmt, str::FromStr};
+
+use crate::{
+    notes::{get_flat_accidentals, Accidental, Note, NUM_NOTES},
+    scales::{Degree, MAJOR_STEPS},
+};
+
+const LETTERS: [char; 7] = ['C', 'D', 'E', 'F', 'G', 'A', 'B'];
+
+/// A note name made of a letter and any number of sharps or flats, e.g. `Eb` or `F##`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SpelledNote {
+    /// Index of the letter in `C D E F G A B`.
+    letter: usize,
+    /// Half steps added to the natural note, negative for flats.
+    accidental: i32,
+}
+
+impl SpelledNote {
+    /// The natural note of the letter `C` to `B`, or `None` for any other letter.
+    pub fn natural(letter: char) -> Option<SpelledNote> {
+        LETTERS
+            .iter()
+            .position(|natural| *natural == letter.to_ascii_uppercase())
+            .map(|letter| SpelledNote {
+                letter,
+                accidental: 0,
+            })
+    }
+
+    /// The name of `note` using the given letter, adding as many sharps or flats as needed.
+    pub fn with_letter(note: Note, letter: usize) -> SpelledNote {
+        let letter = letter % LETTERS.len();
+        let natural = Note::C.transpose(MAJOR_STEPS[letter]);
+        let sharps = natural.interval_to(note) as i32;
+        let accidental = if sharps > (NUM_NOTES / 2) as i32 {
+            sharps - NUM_NOTES as i32
+        } else {
+            sharps
+        };
+        SpelledNote { letter, accidental }
+    }
+
+    pub fn note(&self) -> Note {
+        Note::C.transpose(
+            (MAJOR_STEPS[self.letter] as i32 + self.accidental).rem_euclid(NUM_NOTES as i32)
+                as usize,
+        )
+    }
+
+    /// Number of sharps or flats in the name.
+    pub fn num_accidentals(&self) -> usize {
+        self.accidental.unsigned_abs() as usize
+    }
+}
+
+impl From<Accidental> for SpelledNote {
+    fn from(accidental: Accidental) -> Self {
+        let flat = get_flat_accidentals().contains(&accidental);
+        accidental.to_note().to_str(flat).parse().unwrap()
+    }
+}
+
+impl FromStr for SpelledNote {
+    type Err = String;
+
+    /// Parses a note name such as `E`, `Bb` or `F##`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid note name: '{}'", s.trim());
+        let mut chars = s.trim().chars();
+        let natural = chars
+            .next()
+            .and_then(SpelledNote::natural)
+            .ok_or_else(invalid)?;
+        let accidental = chars
+            .map(|accidental| match accidental {
+                '#' => Ok(1),
+                'b' => Ok(-1),
+                _ => Err(invalid()),
+            })
+            .sum::<Result<i32, String>>()?;
+        Ok(SpelledNote {
+            accidental,
+            ..natural
+        })
+    }
+}
+
+impl fmt::Display for SpelledNote {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let accidental = if self.accidental < 0 { "b" } else { "#" };
+        write!(
+            f,
+            "{}{}",
+            LETTERS[self.letter],
+            accidental.repeat(self.num_accidentals())
+        )
+    }
+}
+
+/// Spells every degree of a scale built on `root`, giving each degree its own letter.
+pub fn spell_scale(root: SpelledNote, degrees: &[Degree]) -> Vec<SpelledNote> {
+    degrees
+        .iter()
+        .map(|degree| {
+            let step = degree.get_step().unwrap_or_default();
+            SpelledNote::with_letter(root.note().transpose(step), root.letter + degree.number - 1)
+        })
+        .collect()
+}
+
+/// Picks the name of `root` that keeps the spelled scale simplest, e.g. `Bb` rather than `A#`
+/// for a major scale but `C#` rather than `Db` for a natural minor scale.
+pub fn spell_root(root: Note, degrees: &[Degree]) -> SpelledNote {
+    let sharp = root.to_str(false).parse::<SpelledNote>().unwrap();
+    let flat = root.to_str(true).parse::<SpelledNote>().unwrap();
+    let num_accidentals = |root: SpelledNote| -> usize {
+        spell_scale(root, degrees)
+            .iter()
+            .map(SpelledNote::num_accidentals)
+            .sum()
+    };
+    if num_accidentals(flat) < num_accidentals(sharp) {
+        flat
+    } else {
+        sharp
+    }
+}
+
+/// How notes are named when they are written out.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Spelling {
+    /// Black keys are named with sharps.
+    #[default]
+    Sharps,
+    /// Black keys are named with flats.
+    Flats,
+    /// The names of the notes of one scale, as given by [`spell_scale`]. Notes outside of the
+    /// scale are named with sharps.
+    Scale(Vec<SpelledNote>),
+}
+
+impl Spelling {
+    pub fn name(&self, note: Note) -> String {
+        match self {
+            Spelling::Sharps => String::from(note.to_str(false)),
+            Spelling::Flats => String::from(note.to_str(true)),
+            Spelling::Scale(spelled_notes) => spelled_notes
+                .iter()
+                .find(|spelled_note| spelled_note.note() == note)
+                .map(|spelled_note| spelled_note.to_string())
+                .unwrap_or_else(|| String::from(note.to_str(false))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scales::Scale;
+
+    fn spell(root: Accidental, scale: Scale) -> String {
+        spell_scale(root.into(), &scale.get_degrees())
+            .iter()
+            .map(|note| note.to_string())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_spell_scale() {
+        assert_eq!(spell(Accidental::D, Scale::Phrygian), "D Eb F G A Bb C");
+        assert_eq!(spell(Accidental::F, Scale::Major), "F G A Bb C D E");
+        assert_eq!(
+            spell(Accidental::GSharp, Scale::HarmonicMinor),
+            "G# A# B C# D# E F##"
+        );
+        assert_eq!(
+            spell(Accidental::GFlat, Scale::Locrian),
+            "Gb Abb Bbb Cb Dbb Ebb Fb"
+        );
+        assert_eq!(spell(Accidental::A, Scale::PentatonicBlues), "A C D Eb E G");
+    }
+
+    #[test]
+    fn test_spell_root() {
+        let major = Scale::Major.get_degrees();
+        let minor = Scale::NaturalMinor.get_degrees();
+        assert_eq!(spell_root(Note::ASharp, &major).to_string(), "Bb");
+        assert_eq!(spell_root(Note::CSharp, &minor).to_string(), "C#");
+        assert_eq!(spell_root(Note::E, &minor).to_string(), "E");
+    }
+
+    #[test]
+    fn test_spelled_note() {
+        assert_eq!(SpelledNote::from(Accidental::EFlat).to_string(), "Eb");
+        assert_eq!(SpelledNote::with_letter(Note::G, 3).to_string(), "F##");
+        assert_eq!("F##".parse::<SpelledNote>().unwrap().note(), Note::G);
+        assert!("Fx".parse::<SpelledNote>().is_err());
+        assert_eq!(SpelledNote::with_letter(Note::B, 0).to_string(), "Cb");
+    }
+}
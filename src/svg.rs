@@ -0,0 +1,274 @@
+use colored::Color;
+use std::fmt::Write;
+
+use crate::{
+    format::{step_color, Format},
+    fret_board::{
+        is_wound_string, lowest_fretted, position_label, FretBoard, Highlight, FRET_LENGTH,
+    },
+};
+
+const MARGIN: usize = 30;
+const STRING_SPACING: usize = 30;
+const OPEN_WIDTH: usize = 40;
+/// Horizontal size of one unit of `FRET_LENGTH`.
+const FRET_UNIT: usize = 12;
+const NOTE_RADIUS: usize = 11;
+const FRET_NUM_HEIGHT: usize = 25;
+
+const SINGLE_INLAYS: [usize; 8] = [3, 5, 7, 9, 15, 17, 19, 21];
+const DOUBLE_INLAYS: [usize; 2] = [12, 24];
+
+/// Draws the fret board as an SVG diagram, highest string on top, with every scale note
+/// labeled, marked and colored like the text output.
+pub fn render_svg(fret_board: &FretBoard, format: &Format) -> String {
+    let num_strings = fret_board.strings.len();
+    let frets = fret_board
+        .strings
+        .first()
+        .map(|string| {
+            string
+                .positions
+                .iter()
+                .map(|position| position.fret)
+                .collect::<Vec<usize>>()
+        })
+        .unwrap_or_default();
+
+    // Left edge of every fret cell, plus the right edge of the last one.
+    let mut edges = vec![MARGIN];
+    for fret in &frets {
+        let width = if *fret == 0 {
+            OPEN_WIDTH
+        } else {
+            FRET_LENGTH[*fret] * FRET_UNIT
+        };
+        edges.push(edges.last().unwrap() + width);
+    }
+    let board_left = if frets.first() == Some(&0) {
+        edges[1]
+    } else {
+        edges[0]
+    };
+    let board_right = *edges.last().unwrap();
+    let board_top = MARGIN;
+    let board_bottom = MARGIN + (num_strings.saturating_sub(1)) * STRING_SPACING;
+    let string_y = |string_index: usize| board_bottom - string_index * STRING_SPACING;
+    let center_x = |index: usize| (edges[index] + edges[index + 1]) / 2;
+
+    let width = board_right + MARGIN;
+    let height = board_bottom + FRET_NUM_HEIGHT + MARGIN;
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="11">"#
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r##"<rect width="{width}" height="{height}" fill="#ffffff"/>"##
+    )
+    .unwrap();
+
+    for (index, fret) in frets.iter().enumerate() {
+        if *fret == 0 {
+            continue;
+        }
+        let x = center_x(index);
+        let middle = (board_top + board_bottom) / 2;
+        if SINGLE_INLAYS.contains(fret) {
+            write_inlay(&mut svg, x, middle);
+        } else if DOUBLE_INLAYS.contains(fret) {
+            let offset = (board_bottom - board_top) / 4;
+            write_inlay(&mut svg, x, middle - offset);
+            write_inlay(&mut svg, x, middle + offset);
+        }
+    }
+
+    for (index, fret) in frets.iter().enumerate() {
+        if *fret == 0 {
+            continue;
+        }
+        let x = edges[index + 1];
+        writeln!(
+            svg,
+            r##"<line x1="{x}" y1="{board_top}" x2="{x}" y2="{board_bottom}" stroke="#999999" stroke-width="2"/>"##
+        )
+        .unwrap();
+    }
+    let nut_width = if frets.first() == Some(&0) { 6 } else { 2 };
+    writeln!(
+        svg,
+        r##"<line x1="{board_left}" y1="{board_top}" x2="{board_left}" y2="{board_bottom}" stroke="#333333" stroke-width="{nut_width}"/>"##
+    )
+    .unwrap();
+
+    for string_index in 0..num_strings {
+        let y = string_y(string_index);
+        let stroke_width = if is_wound_string(string_index, num_strings) {
+            2
+        } else {
+            1
+        };
+        writeln!(
+            svg,
+            r##"<line x1="{board_left}" y1="{y}" x2="{board_right}" y2="{y}" stroke="#555555" stroke-width="{stroke_width}"/>"##
+        )
+        .unwrap();
+    }
+
+    let lowest_fret = lowest_fretted(fret_board);
+    for (string_index, position) in fret_board.scale_positions() {
+        let index = frets
+            .iter()
+            .position(|fret| *fret == position.fret)
+            .unwrap();
+        let label = position_label(position, lowest_fret, format);
+        let (label, (fill, text)) = match position.highlight {
+            Highlight::Dimmed => (label, DIMMED_COLORS),
+            Highlight::Hidden => (String::from("?"), note_colors(None)),
+            Highlight::First => (
+                format!("({})", label),
+                note_colors(position.step.and_then(step_color)),
+            ),
+            Highlight::Second => (
+                format!("[{}]", label),
+                note_colors(position.step.and_then(step_color)),
+            ),
+            Highlight::Normal => (label, note_colors(position.step.and_then(step_color))),
+        };
+        let x = center_x(index);
+        let y = string_y(string_index);
+        writeln!(
+            svg,
+            r#"<circle cx="{x}" cy="{y}" r="{NOTE_RADIUS}" fill="{fill}" stroke="{text}" stroke-width="1"/>"#
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r#"<text x="{x}" y="{y}" fill="{text}" text-anchor="middle" dominant-baseline="central">{label}</text>"#
+        )
+        .unwrap();
+    }
+
+    let fret_num_y = board_bottom + FRET_NUM_HEIGHT;
+    for (index, fret) in frets.iter().enumerate() {
+        if *fret == 0 {
+            continue;
+        }
+        writeln!(
+            svg,
+            r##"<text x="{}" y="{fret_num_y}" fill="#333333" text-anchor="middle">{fret}</text>"##,
+            center_x(index)
+        )
+        .unwrap();
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn write_inlay(svg: &mut String, x: usize, y: usize) {
+    writeln!(svg, r##"<circle cx="{x}" cy="{y}" r="6" fill="#dddddd"/>"##).unwrap();
+}
+
+/// Fill and text color of a dimmed note.
+const DIMMED_COLORS: (&str, &str) = ("#eeeeee", "#999999");
+
+/// Fill and text color of a note, matching the terminal colors of `format_with_color`.
+fn note_colors(color: Option<Color>) -> (&'static str, &'static str) {
+    match color {
+        Some(Color::Green) => ("#2e9e44", "#ffffff"),
+        Some(Color::Red) => ("#d33c3c", "#ffffff"),
+        Some(Color::Cyan) => ("#3cc4d3", "#000000"),
+        Some(Color::Black) => ("#222222", "#ffffff"),
+        Some(Color::Blue) => ("#3c64d3", "#ffffff"),
+        Some(Color::Magenta) => ("#b43cd3", "#ffffff"),
+        Some(Color::Yellow) => ("#e8c930", "#000000"),
+        _ => ("#ffffff", "#000000"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        format::Labels,
+        fret_board::{build_fret_board, FRET_SPAN},
+        notes::Note,
+        scales::Scale,
+        spelling::Spelling,
+        tunings::Tuning,
+    };
+
+    #[test]
+    fn test_render_svg() {
+        let fret_board = build_fret_board(
+            &Tuning::StandardB7.get_pitches(),
+            0,
+            FRET_SPAN,
+            &Scale::Major.get_notes(Note::C),
+        );
+        let svg = render_svg(&fret_board, &Format::default());
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(
+            svg.matches("<circle").count(),
+            fret_board.scale_positions().count() + 1
+        );
+        assert_eq!(svg.matches(r#"stroke-width="2"/>"#).count(), 4 + 4);
+        assert!(svg.contains(r##"fill="#2e9e44" stroke="#ffffff" stroke-width="1"/>"##));
+    }
+
+    #[test]
+    fn test_render_svg_high_frets() {
+        let fret_board = build_fret_board(
+            &Tuning::StandardE6.get_pitches(),
+            20,
+            FRET_SPAN,
+            &Scale::PentatonicMinor.get_notes(Note::A),
+        );
+        let svg = render_svg(&fret_board, &Format::default());
+        assert!(svg.contains(">24</text>"));
+        assert_eq!(svg.matches(r##"fill="#dddddd""##).count(), 2 + 1);
+    }
+
+    #[test]
+    fn test_render_svg_labels() {
+        let mut fret_board = build_fret_board(
+            &Tuning::StandardE6.get_pitches(),
+            5,
+            FRET_SPAN,
+            &Scale::PentatonicMinor.get_notes(Note::A),
+        );
+        let text = |svg: &str, label: &str| {
+            svg.matches(&format!(r#"dominant-baseline="central">{}</text>"#, label))
+                .count()
+        };
+        let intervals = Format {
+            spelling: Spelling::Sharps,
+            labels: Labels::Intervals,
+            ..Default::default()
+        };
+        let svg = render_svg(&fret_board, &intervals);
+        assert_eq!(text(&svg, "R"), 3);
+        assert_eq!(text(&svg, "b3"), 3);
+        assert_eq!(text(&svg, "A"), 0);
+
+        // Hidden notes never give their names away, and the notes of compared scales are marked.
+        let c_before = text(&render_svg(&fret_board, &Format::default()), "C");
+        for position in &mut fret_board.strings[0].positions {
+            position.highlight = match position.fret {
+                5 => Highlight::First,
+                8 => Highlight::Hidden,
+                _ => Highlight::Normal,
+            };
+        }
+        fret_board.strings[1].positions[2].highlight = Highlight::Second;
+        let svg = render_svg(&fret_board, &Format::default());
+        assert_eq!(text(&svg, "?"), 1);
+        assert_eq!(text(&svg, "C"), c_before - 1);
+        assert_eq!(text(&svg, "(A)"), 1);
+        assert_eq!(text(&svg, "[E]"), 1);
+    }
+}
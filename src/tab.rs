@@ -0,0 +1,174 @@
+//! Tablature of the scale played up the fret window and back down.
+
+use crate::{
+    fret_board::{FretBoard, FretPosition},
+    playback::ascending_positions,
+};
+
+/// Width every fret number is padded to, which keeps single digit frets apart.
+const MIN_FRET_WIDTH: usize = 2;
+
+/// The notes of the run, each as its string index counted from the lowest string and its fret.
+///
+/// The run climbs through [`ascending_positions`] and comes back down. With `root_string`,
+/// counted from the highest string starting at 1 like the lines of a tab, it starts and ends
+/// on the lowest root of that string instead of the lowest note of the window. The top note is
+/// played twice if `repeat_top` is set.
+pub fn tab_run(
+    fret_board: &FretBoard,
+    root_string: Option<usize>,
+    repeat_top: bool,
+) -> Result<Vec<(usize, usize)>, String> {
+    let ascending = ascending_positions(fret_board);
+    let start = match root_string {
+        Some(root_string) => {
+            let string_index = fret_board
+                .strings
+                .len()
+                .checked_sub(root_string)
+                .filter(|_| root_string > 0)
+                .ok_or_else(|| {
+                    format!(
+                        "There is no string {}, the tuning has {} strings",
+                        root_string,
+                        fret_board.strings.len()
+                    )
+                })?;
+            ascending
+                .iter()
+                .position(|(index, position)| *index == string_index && position.step == Some(0))
+                .ok_or_else(|| {
+                    format!(
+                        "There is no root on string {} in this position",
+                        root_string
+                    )
+                })?
+        }
+        None => 0,
+    };
+    let up: Vec<(usize, usize)> = ascending[start..]
+        .iter()
+        .map(|(string_index, &FretPosition { fret, .. })| (*string_index, fret))
+        .collect();
+    let skip = if repeat_top { 0 } else { 1 };
+    let down = up.iter().rev().skip(skip).copied();
+    Ok(up.iter().copied().chain(down).collect())
+}
+
+/// Renders the run as tab, one line per string from the highest to the lowest, each starting
+/// with the name of the open string. The highest string is written in lower case when it has
+/// the same name as the lowest, as in `e|` over `E|`.
+pub fn render_tab(fret_board: &FretBoard, run: &[(usize, usize)]) -> Vec<String> {
+    let names: Vec<String> = fret_board
+        .strings
+        .iter()
+        .map(|string| String::from(string.open_pitch.note.to_str(false)))
+        .collect();
+    let name_width = names.iter().map(String::len).max().unwrap_or_default();
+    let fret_width = run
+        .iter()
+        .map(|(_, fret)| fret.to_string().len())
+        .max()
+        .unwrap_or_default()
+        .max(MIN_FRET_WIDTH);
+
+    let mut lines = Vec::new();
+    for (string_index, name) in names.iter().enumerate().rev() {
+        let name = if string_index + 1 == names.len() && names.len() > 1 && *name == names[0] {
+            name.to_lowercase()
+        } else {
+            name.clone()
+        };
+        let mut line = format!("{:<name_width$}|-", name);
+        for (note_string, fret) in run {
+            if *note_string == string_index {
+                line.push_str(&format!("{:-<fret_width$}-", fret));
+            } else {
+                line.push_str(&"-".repeat(fret_width + 1));
+            }
+        }
+        line.push('|');
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fret_board::{build_fret_board, FRET_SPAN},
+        notes::Note,
+        scales::Scale,
+        tunings::{Tuning, TuningSpec},
+    };
+
+    fn a_minor_pentatonic() -> FretBoard {
+        build_fret_board(
+            &Tuning::StandardE6.get_pitches(),
+            5,
+            FRET_SPAN,
+            &Scale::PentatonicMinor.get_notes(Note::A),
+        )
+    }
+
+    #[test]
+    fn test_tab_run() {
+        let fret_board = a_minor_pentatonic();
+        let run = tab_run(&fret_board, None, false).unwrap();
+        assert_eq!(run.len(), 23);
+        assert_eq!(run[..3], [(0, 5), (0, 8), (1, 5)]);
+        assert_eq!(run[11], (5, 8));
+        assert_eq!(run[12], (5, 5));
+        assert_eq!(run.last(), Some(&(0, 5)));
+
+        let run = tab_run(&fret_board, None, true).unwrap();
+        assert_eq!(run.len(), 24);
+        assert_eq!(run[11], run[12]);
+
+        // The root on the D string is its seventh fret.
+        let run = tab_run(&fret_board, Some(4), false).unwrap();
+        assert_eq!(run.first(), Some(&(2, 7)));
+        assert_eq!(run.last(), Some(&(2, 7)));
+        assert_eq!(run.len(), 13);
+        assert!(tab_run(&fret_board, Some(5), false).is_err());
+        assert!(tab_run(&fret_board, Some(7), false).is_err());
+        assert!(tab_run(&fret_board, Some(0), false).is_err());
+
+        // Strings are not capped by the number of notes in an octave.
+        let thirteen_strings: TuningSpec = "e,a,d,g,b,e,a,d,g,b,e,a,d".parse().unwrap();
+        let fret_board = build_fret_board(
+            &thirteen_strings.get_pitches(),
+            5,
+            FRET_SPAN,
+            &Scale::PentatonicMinor.get_notes(Note::A),
+        );
+        let run = tab_run(&fret_board, Some(13), false).unwrap();
+        assert_eq!(run.first(), Some(&(0, 5)));
+        assert!(tab_run(&fret_board, Some(14), false)
+            .unwrap_err()
+            .contains("the tuning has 13 strings"));
+    }
+
+    #[test]
+    fn test_render_tab() {
+        let fret_board = a_minor_pentatonic();
+        let run = tab_run(&fret_board, Some(1), true).unwrap();
+        assert_eq!(
+            render_tab(&fret_board, &run),
+            vec![
+                "e|-5--8--8--5--|",
+                "B|-------------|",
+                "G|-------------|",
+                "D|-------------|",
+                "A|-------------|",
+                "E|-------------|",
+            ]
+        );
+        let run = tab_run(&fret_board, None, false).unwrap();
+        let tab = render_tab(&fret_board, &run);
+        assert_eq!(tab.len(), 6);
+        assert!(tab[5].starts_with("E|-5--8-----"));
+        assert!(tab.iter().all(|line| line.len() == tab[0].len()));
+    }
+}
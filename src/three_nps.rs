@@ -0,0 +1,171 @@
+//! Three-notes-per-string patterns, which run up the scale playing exactly three notes on every
+//! string before moving to the next one.
+//!
+//! The pattern starts on the lowest string at a chosen degree of the scale, and every string
+//! continues with the scale notes following the last note of the string below it. Nothing
+//! depends on the intervals between the strings, so the patterns work in any tuning; the
+//! window of frets simply grows to fit them.
+
+use crate::{
+    fret_board::{build_fret_board, FretBoard, NUM_FRETS},
+    notes::{Note, NUM_NOTES},
+    pitch::Pitch,
+};
+
+/// Number of scale notes played on every string.
+pub const NOTES_PER_STRING: usize = 3;
+
+/// The frets played on every string, from the lowest string to the highest, starting at
+/// `start_degree` of the scale (1 for the root) as low on the neck as the pattern fits.
+pub fn three_nps_frets(
+    tuning: &[Pitch],
+    notes_in_scale: &[(Note, usize)],
+    start_degree: usize,
+) -> Result<Vec<Vec<usize>>, String> {
+    let (start_note, _) = start_degree
+        .checked_sub(1)
+        .and_then(|index| notes_in_scale.get(index))
+        .ok_or_else(|| {
+            format!(
+                "The scale has no degree {}, it has {} notes",
+                start_degree,
+                notes_in_scale.len()
+            )
+        })?;
+    let lowest_string = tuning.first().ok_or("The tuning has no strings")?;
+    let first_fret = lowest_string.note.interval_to(*start_note);
+
+    // A string tuned far above the one below it may need the pattern an octave higher.
+    for octave in 0..=NUM_FRETS / NUM_NOTES {
+        let mut pitch = lowest_string.transpose(first_fret + octave * NUM_NOTES);
+        let mut frets = Vec::with_capacity(tuning.len());
+        for string in tuning {
+            let mut string_frets = Vec::with_capacity(NOTES_PER_STRING);
+            for _ in 0..NOTES_PER_STRING {
+                string_frets.push(pitch.midi_number() - string.midi_number());
+                pitch = next_in_scale(pitch, notes_in_scale);
+            }
+            frets.push(string_frets);
+        }
+        let all_frets = || frets.iter().flatten();
+        if all_frets().any(|fret| *fret < 0) {
+            continue;
+        }
+        if all_frets().any(|fret| *fret > NUM_FRETS as i32) {
+            break;
+        }
+        return Ok(frets
+            .into_iter()
+            .map(|string_frets| string_frets.into_iter().map(|fret| fret as usize).collect())
+            .collect());
+    }
+    Err(String::from(
+        "The three-notes-per-string pattern doesn't fit on the neck in this tuning",
+    ))
+}
+
+/// The lowest pitch above `pitch` that belongs to the scale.
+fn next_in_scale(pitch: Pitch, notes_in_scale: &[(Note, usize)]) -> Pitch {
+    (1..=NUM_NOTES)
+        .map(|semitones| pitch.transpose(semitones))
+        .find(|next| notes_in_scale.iter().any(|(note, _)| *note == next.note))
+        .unwrap_or(pitch)
+}
+
+/// The starting fret and the number of frets of the pattern.
+pub fn three_nps_window(
+    tuning: &[Pitch],
+    notes_in_scale: &[(Note, usize)],
+    start_degree: usize,
+) -> Result<(usize, usize), String> {
+    let frets = three_nps_frets(tuning, notes_in_scale, start_degree)?;
+    Ok(window(&frets))
+}
+
+fn window(frets: &[Vec<usize>]) -> (usize, usize) {
+    let lowest = frets.iter().flatten().min().copied().unwrap_or_default();
+    let highest = frets.iter().flatten().max().copied().unwrap_or_default();
+    (lowest, highest - lowest + 1)
+}
+
+/// Lays out the scale as a three-notes-per-string pattern, leaving every other scale note in
+/// the window unmarked.
+pub fn build_three_nps_fret_board(
+    tuning: &[Pitch],
+    notes_in_scale: &[(Note, usize)],
+    start_degree: usize,
+) -> Result<FretBoard, String> {
+    let frets = three_nps_frets(tuning, notes_in_scale, start_degree)?;
+    let (starting_fret, fret_span) = window(&frets);
+    let mut fret_board = build_fret_board(tuning, starting_fret, fret_span, notes_in_scale);
+    for (string, string_frets) in fret_board.strings.iter_mut().zip(&frets) {
+        for position in &mut string.positions {
+            if !string_frets.contains(&position.fret) {
+                position.step = None;
+            }
+        }
+    }
+    Ok(fret_board)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{scales::Scale, tunings::Tuning};
+
+    #[test]
+    fn test_three_nps_frets() {
+        let standard = Tuning::StandardE6.get_pitches();
+        let g_major = Scale::Major.get_notes(Note::G);
+        assert_eq!(
+            three_nps_frets(&standard, &g_major, 1).unwrap(),
+            vec![
+                vec![3, 5, 7],
+                vec![3, 5, 7],
+                vec![4, 5, 7],
+                vec![4, 5, 7],
+                vec![5, 7, 8],
+                vec![5, 7, 8],
+            ]
+        );
+        assert_eq!(three_nps_window(&standard, &g_major, 1), Ok((3, 6)));
+        // Starting on the sixth degree, E, uses the open strings.
+        assert_eq!(three_nps_window(&standard, &g_major, 6), Ok((0, 6)));
+        assert!(three_nps_frets(&standard, &g_major, 8).is_err());
+        assert!(three_nps_frets(&standard, &g_major, 0).is_err());
+
+        // Every string plays the next three notes, whatever the tuning.
+        for tuning in [Tuning::AllFourths7, Tuning::OpenG6, Tuning::StandardB7] {
+            let pitches = tuning.get_pitches();
+            for degree in 1..=7 {
+                let frets = three_nps_frets(&pitches, &g_major, degree).unwrap();
+                let played: Vec<Pitch> = frets
+                    .iter()
+                    .zip(&pitches)
+                    .flat_map(|(frets, string)| frets.iter().map(|fret| string.transpose(*fret)))
+                    .collect();
+                assert_eq!(played.len(), pitches.len() * NOTES_PER_STRING);
+                assert!(played.windows(2).all(|pair| pair[0] < pair[1]));
+                assert!(played
+                    .iter()
+                    .all(|pitch| g_major.iter().any(|(note, _)| *note == pitch.note)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_build_three_nps_fret_board() {
+        let tuning = Tuning::AllFourths7.get_pitches();
+        let notes = Scale::Dorian.get_notes(Note::A);
+        let fret_board = build_three_nps_fret_board(&tuning, &notes, 1).unwrap();
+        for string in &fret_board.strings {
+            let marked = string.positions.iter().filter(|p| p.step.is_some()).count();
+            assert_eq!(marked, NOTES_PER_STRING);
+        }
+        assert_eq!(fret_board.scale_positions().next().unwrap().1.step, Some(0));
+
+        // The pentatonic scale climbs too far on seven strings.
+        let notes = Scale::PentatonicMinor.get_notes(Note::A);
+        assert!(build_three_nps_fret_board(&tuning, &notes, 1).is_err());
+    }
+}
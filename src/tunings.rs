@@ -1,9 +1,14 @@
 use clap::ValueEnum;
-use strum::Display;
+use std::{fmt, str::FromStr};
+use strum::{Display, EnumIter};
 
-use crate::notes::Note;
+use crate::{
+    notes::Note,
+    pitch::{infer_open_pitches, Pitch},
+};
 
-#[derive(Debug, Clone, Copy, ValueEnum, Display)]
+/// The built-in tunings, listed by the notes of their open strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, EnumIter, Display)]
 pub enum Tuning {
     #[strum(to_string = "Standard E (6 string)")]
     StandardE6,
@@ -40,6 +45,7 @@ pub enum Tuning {
 }
 
 impl Tuning {
+    /// Open string notes, ordered from the lowest string to the highest.
     pub fn get_notes(&self) -> &'static [Note] {
         match self {
             Tuning::StandardE6 => &[Note::E, Note::A, Note::D, Note::G, Note::B, Note::E],
@@ -113,11 +119,239 @@ impl Tuning {
             ],
         }
     }
+
+    /// Open string pitches, ordered from the lowest string to the highest.
+    pub fn get_pitches(&self) -> Vec<Pitch> {
+        let octaves: &[i32] = match self {
+            Tuning::StandardE6 => &[2, 2, 3, 3, 3, 4],
+            Tuning::OpenG6 => &[2, 2, 3, 3, 3, 4],
+            Tuning::OpenE6 => &[2, 2, 3, 3, 3, 4],
+            Tuning::OpenD6 => &[2, 2, 3, 3, 3, 4],
+            Tuning::OpenC6 => &[2, 2, 3, 3, 4, 4],
+            Tuning::OpenA6 => &[2, 2, 3, 3, 4, 4],
+            Tuning::DropD6 => &[2, 2, 3, 3, 3, 4],
+            Tuning::StandardD6 => &[2, 2, 3, 3, 3, 4],
+            Tuning::DropCSharp6 => &[2, 2, 3, 3, 3, 4],
+            Tuning::StandardCSharp6 => &[2, 2, 3, 3, 3, 4],
+            Tuning::DropC6 => &[2, 2, 3, 3, 3, 4],
+            Tuning::StandardC6 => &[2, 2, 2, 3, 3, 4],
+            Tuning::StandardB7 => &[1, 2, 2, 3, 3, 3, 4],
+            Tuning::DropA7 => &[1, 2, 2, 3, 3, 3, 4],
+            Tuning::StandardA7 => &[1, 2, 2, 3, 3, 3, 4],
+            Tuning::AllFourths7 => &[1, 2, 2, 3, 3, 4, 4],
+        };
+        self.get_notes()
+            .iter()
+            .zip(octaves)
+            .map(|(note, octave)| Pitch::new(*note, *octave))
+            .collect()
+    }
+}
+
+impl FromStr for Tuning {
+    type Err = String;
+
+    /// Looks up a tuning by its command-line name, e.g. `drop-d6`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        <Tuning as ValueEnum>::from_str(s, true).map_err(|_| format!("Unknown tuning: {}", s))
+    }
+}
+
+/// Open strings of a user-defined tuning, ordered from the lowest string to the highest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomTuning {
+    notes: Vec<Note>,
+    pitches: Vec<Pitch>,
+}
+
+impl CustomTuning {
+    /// A tuning given by note names only, with the octaves picked by [`infer_open_pitches`].
+    pub fn from_notes(notes: Vec<Note>) -> CustomTuning {
+        let pitches = infer_open_pitches(&notes);
+        CustomTuning { notes, pitches }
+    }
+
+    pub fn from_pitches(pitches: Vec<Pitch>) -> CustomTuning {
+        let notes = pitches.iter().map(|pitch| pitch.note).collect();
+        CustomTuning { notes, pitches }
+    }
+
+    pub fn get_notes(&self) -> &[Note] {
+        &self.notes
+    }
+
+    pub fn get_pitches(&self) -> &[Pitch] {
+        &self.pitches
+    }
+}
+
+/// Either one of the built-in tunings or a user-defined list of open string notes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TuningSpec {
+    Preset(Tuning),
+    Custom(CustomTuning),
+}
+
+impl TuningSpec {
+    /// Open string notes, ordered from the lowest string to the highest.
+    pub fn get_notes(&self) -> &[Note] {
+        match self {
+            TuningSpec::Preset(tuning) => tuning.get_notes(),
+            TuningSpec::Custom(tuning) => tuning.get_notes(),
+        }
+    }
+
+    /// Open string pitches, ordered from the lowest string to the highest.
+    pub fn get_pitches(&self) -> Vec<Pitch> {
+        match self {
+            TuningSpec::Preset(tuning) => tuning.get_pitches(),
+            TuningSpec::Custom(tuning) => tuning.get_pitches().to_vec(),
+        }
+    }
+
+    /// Command-line name of the tuning, which parses back into the same tuning.
+    pub fn key(&self) -> String {
+        match self {
+            TuningSpec::Preset(tuning) => tuning
+                .to_possible_value()
+                .map(|value| value.get_name().to_string())
+                .unwrap_or_default(),
+            TuningSpec::Custom(tuning) => tuning
+                .get_pitches()
+                .iter()
+                .map(|pitch| pitch.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        }
+    }
+}
+
+impl From<Tuning> for TuningSpec {
+    fn from(tuning: Tuning) -> Self {
+        TuningSpec::Preset(tuning)
+    }
+}
+
+impl fmt::Display for TuningSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TuningSpec::Preset(tuning) => write!(f, "{}", tuning),
+            TuningSpec::Custom(tuning) => write!(
+                f,
+                "Custom {} ({} string)",
+                tuning
+                    .get_notes()
+                    .iter()
+                    .map(|note| note.to_str(false))
+                    .collect::<Vec<&str>>()
+                    .join(" "),
+                tuning.get_notes().len()
+            ),
+        }
+    }
 }
 
+impl FromStr for TuningSpec {
+    type Err = String;
+
+    /// Accepts either a tuning name such as `drop-d6` or comma separated open string notes
+    /// from the lowest string to the highest, such as `D,A,D,G,A,D`. The notes can also be
+    /// given with their octave, such as `D2,A2,D3,G3,A3,D4`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !s.contains(',') {
+            return s.parse::<Tuning>().map(TuningSpec::Preset).map_err(|_| {
+                format!(
+                    "Unknown tuning: '{}' (use a tuning name or comma separated notes such as D,A,D,G,A,D)",
+                    s
+                )
+            });
+        }
+        let names: Vec<&str> = s.split(',').map(str::trim).collect();
+        if names.len() < MIN_STRINGS {
+            return Err(format!(
+                "A tuning needs at least {} strings, got {} in '{}'",
+                MIN_STRINGS,
+                names.len(),
+                s
+            ));
+        }
+        let with_octave = |name: &&str| name.ends_with(|c: char| c.is_ascii_digit());
+        if names.iter().all(with_octave) {
+            let pitches = names
+                .iter()
+                .map(|name| {
+                    name.parse::<Pitch>()
+                        .map_err(|error| format!("{} in tuning '{}'", error, s))
+                })
+                .collect::<Result<Vec<Pitch>, String>>()?;
+            return Ok(TuningSpec::Custom(CustomTuning::from_pitches(pitches)));
+        }
+        if names.iter().any(with_octave) {
+            return Err(format!(
+                "Give either every string an octave or none of them in tuning '{}' (e.g. D2,A2,D3,G3,A3,D4)",
+                s
+            ));
+        }
+        let notes = names
+            .iter()
+            .map(|name| {
+                name.parse::<Note>()
+                    .map_err(|error| format!("{} in tuning '{}'", error, s))
+            })
+            .collect::<Result<Vec<Note>, String>>()?;
+        Ok(TuningSpec::Custom(CustomTuning::from_notes(notes)))
+    }
+}
+
+const MIN_STRINGS: usize = 2;
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_tuning_spec_from_str() {
+        assert_eq!(
+            "drop-d6".parse::<TuningSpec>(),
+            Ok(TuningSpec::Preset(Tuning::DropD6))
+        );
+        assert_eq!(
+            "D, A, D, G, A, D".parse::<TuningSpec>(),
+            Ok(TuningSpec::Custom(CustomTuning::from_notes(vec![
+                Note::D,
+                Note::A,
+                Note::D,
+                Note::G,
+                Note::A,
+                Note::D
+            ])))
+        );
+        assert_eq!(
+            "F#,B,E,A,C#,F#".parse::<TuningSpec>().unwrap().get_notes()[4],
+            Note::CSharp
+        );
+        assert!("dadgad".parse::<TuningSpec>().is_err());
+        assert!("D,A,D,G,A,".parse::<TuningSpec>().is_err());
+        assert!("D,A,H,G,A,D".parse::<TuningSpec>().is_err());
+        assert!("E,".parse::<TuningSpec>().is_err());
+        assert_eq!(
+            "G4,C4,E4,A4".parse::<TuningSpec>().unwrap().get_pitches()[0],
+            Pitch::new(Note::G, 4)
+        );
+        assert!("E1,A,D,G".parse::<TuningSpec>().is_err());
+    }
+
+    #[test]
+    fn test_key() {
+        for key in ["drop-c-sharp6", "D2,A2,D3,G3,A3,D4"] {
+            assert_eq!(key.parse::<TuningSpec>().unwrap().key(), key);
+        }
+        assert_eq!(
+            "D,A,D,G,A,D".parse::<TuningSpec>().unwrap().key(),
+            "D2,A2,D3,G3,A3,D4"
+        );
+    }
 
     #[test]
     fn test_get_notes() {
@@ -145,4 +379,19 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn test_get_pitches() {
+        for tuning in Tuning::iter() {
+            assert_eq!(tuning.get_pitches(), infer_open_pitches(tuning.get_notes()));
+        }
+        assert_eq!(
+            Tuning::OpenC6.get_pitches()[4],
+            "C4".parse::<Pitch>().unwrap()
+        );
+        assert_eq!(
+            "B,E,A,D,F#,B".parse::<TuningSpec>().unwrap().get_pitches()[0],
+            Pitch::new(Note::B, 1)
+        );
+    }
 }
@@ -0,0 +1,163 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::f64::consts::PI;
+
+use crate::{pitch::Pitch, playback::NoteValue};
+
+const SAMPLE_RATE: u32 = 44_100;
+/// How much of its energy a plucked string keeps on every pass through the delay line.
+const DECAY: f64 = 0.996;
+/// Length of the fade at the end of every note, so cutting it off doesn't click.
+const FADE_SECONDS: f64 = 0.01;
+const CLICK_SECONDS: f64 = 0.03;
+/// Loudest sample after normalizing, leaving some headroom.
+const PEAK: f64 = 0.8;
+
+/// Synthesizes the pitches as a plucked string, one after another at `tempo` quarter notes per
+/// minute, each lasting `note_value`, and encodes them as a 16-bit mono WAV file.
+///
+/// When `count_in` is not zero, that many metronome clicks are played on the quarter notes
+/// before the first note. `a4` is the frequency of `A4` in Hz.
+pub fn render_wav(
+    pitches: &[Pitch],
+    tempo: u32,
+    note_value: NoteValue,
+    count_in: u32,
+    a4: f64,
+) -> Vec<u8> {
+    let quarter_samples = (SAMPLE_RATE as f64 * 60.0 / tempo.max(1) as f64) as usize;
+    let note_samples = quarter_samples * 4 / note_value.divisions() as usize;
+    let count_in_samples = quarter_samples * count_in as usize;
+
+    let mut samples = vec![0.0; count_in_samples + note_samples * pitches.len()];
+    for beat in 0..count_in as usize {
+        let accent = beat % 4 == 0;
+        mix(&mut samples, beat * quarter_samples, &click(accent));
+    }
+    for (index, pitch) in pitches.iter().enumerate() {
+        let seed = pitch.midi_number() as u64 + index as u64;
+        let note = pluck(pitch.frequency(a4), note_samples, seed);
+        mix(&mut samples, count_in_samples + index * note_samples, &note);
+    }
+
+    let peak = samples
+        .iter()
+        .fold(0.0, |peak: f64, sample| peak.max(sample.abs()));
+    if peak > 0.0 {
+        samples.iter_mut().for_each(|sample| *sample *= PEAK / peak);
+    }
+    encode_wav(&samples)
+}
+
+/// A plucked string at `frequency`, using the Karplus–Strong algorithm: a burst of noise runs
+/// through a delay line one period long and is smoothed a little more on every pass.
+fn pluck(frequency: f64, num_samples: usize, seed: u64) -> Vec<f64> {
+    let period = ((SAMPLE_RATE as f64 / frequency).round() as usize).max(2);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut delay_line: Vec<f64> = (0..period).map(|_| rng.random_range(-1.0..1.0)).collect();
+
+    let fade_samples = ((SAMPLE_RATE as f64 * FADE_SECONDS) as usize).min(num_samples);
+    let mut samples = Vec::with_capacity(num_samples);
+    for index in 0..num_samples {
+        let position = index % period;
+        let next = delay_line[(index + 1) % period];
+        let sample = delay_line[position];
+        delay_line[position] = DECAY * 0.5 * (sample + next);
+
+        let remaining = num_samples - index;
+        let fade = if remaining < fade_samples {
+            remaining as f64 / fade_samples as f64
+        } else {
+            1.0
+        };
+        samples.push(sample * fade);
+    }
+    samples
+}
+
+/// A short decaying metronome click, higher pitched on the first beat of a bar.
+fn click(accent: bool) -> Vec<f64> {
+    let frequency = if accent { 1500.0 } else { 1000.0 };
+    let num_samples = (SAMPLE_RATE as f64 * CLICK_SECONDS) as usize;
+    (0..num_samples)
+        .map(|index| {
+            let time = index as f64 / SAMPLE_RATE as f64;
+            let envelope = 1.0 - index as f64 / num_samples as f64;
+            envelope * envelope * (2.0 * PI * frequency * time).sin()
+        })
+        .collect()
+}
+
+fn mix(samples: &mut [f64], offset: usize, sound: &[f64]) {
+    for (sample, value) in samples[offset..].iter_mut().zip(sound) {
+        *sample += value;
+    }
+}
+
+/// Encodes samples between -1 and 1 as a 16-bit mono PCM WAV file.
+fn encode_wav(samples: &[f64]) -> Vec<u8> {
+    let data_len = samples.len() as u32 * 2;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    // PCM, one channel.
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f64) as i16;
+        wav.extend_from_slice(&value.to_le_bytes());
+    }
+    wav
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{notes::Note, pitch::A4_FREQUENCY};
+
+    #[test]
+    fn test_pluck() {
+        let samples = pluck(110.0, SAMPLE_RATE as usize, 1);
+        assert_eq!(samples.len(), SAMPLE_RATE as usize);
+        // The note rings at the frequency of the string: one period later the wave repeats.
+        let period = 401;
+        let correlation: f64 = (1000..2000)
+            .map(|index| samples[index] * samples[index + period])
+            .sum();
+        assert!(correlation > 0.0);
+        let energy = |range: std::ops::Range<usize>| -> f64 {
+            samples[range].iter().map(|sample| sample * sample).sum()
+        };
+        assert!(energy(0..4410) > energy(30000..34410));
+        assert!(samples.last().unwrap().abs() < 0.01);
+    }
+
+    #[test]
+    fn test_render_wav() {
+        let pitches = [Pitch::new(Note::E, 2), Pitch::new(Note::B, 1)];
+        let wav = render_wav(&pitches, 120, NoteValue::Quarter, 4, A4_FREQUENCY);
+
+        assert_eq!(&wav[..4], b"RIFF");
+        assert_eq!(&wav[8..16], b"WAVEfmt ");
+        assert_eq!(&wav[36..40], b"data");
+        // Four clicks and two notes of half a second each.
+        let num_samples = 6 * SAMPLE_RATE as usize / 2;
+        assert_eq!(wav.len(), 44 + num_samples * 2);
+        assert_eq!(
+            u32::from_le_bytes(wav[40..44].try_into().unwrap()) as usize,
+            num_samples * 2
+        );
+        assert_eq!(
+            wav,
+            render_wav(&pitches, 120, NoteValue::Quarter, 4, A4_FREQUENCY)
+        );
+    }
+}